@@ -1,5 +1,5 @@
 use crate::{ecore, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
-use pyo3::exceptions::PyTypeError;
+use pyo3::exceptions::{PyTypeError, PyValueError};
 use pyo3::prelude::*;
 use pyo3::types::PyByteArray;
 use pyo3::types::PyDict;
@@ -16,6 +16,17 @@ pub enum CharSet {
     UTF_8,
     US_ASCII,
     UTF_16BE,
+    UTF_16LE,
+    UTF_16,
+    ISO_8859_1,
+    ISO_8859_15,
+    WINDOWS_1252,
+    SHIFT_JIS,
+    EUC_JP,
+    EUC_KR,
+    GBK,
+    BIG5,
+    KOI8_R,
 }
 
 impl From<CharSet> for ecore::CharSet {
@@ -24,6 +35,42 @@ impl From<CharSet> for ecore::CharSet {
             CharSet::UTF_8 => ecore::CharSet::UTF_8,
             CharSet::US_ASCII => ecore::CharSet::US_ASCII,
             CharSet::UTF_16BE => ecore::CharSet::UTF_16BE,
+            CharSet::UTF_16LE => ecore::CharSet::UTF_16LE,
+            CharSet::UTF_16 => ecore::CharSet::UTF_16,
+            CharSet::ISO_8859_1 => ecore::CharSet::ISO_8859_1,
+            CharSet::ISO_8859_15 => ecore::CharSet::ISO_8859_15,
+            CharSet::WINDOWS_1252 => ecore::CharSet::WINDOWS_1252,
+            CharSet::SHIFT_JIS => ecore::CharSet::SHIFT_JIS,
+            CharSet::EUC_JP => ecore::CharSet::EUC_JP,
+            CharSet::EUC_KR => ecore::CharSet::EUC_KR,
+            CharSet::GBK => ecore::CharSet::GBK,
+            CharSet::BIG5 => ecore::CharSet::BIG5,
+            CharSet::KOI8_R => ecore::CharSet::KOI8_R,
+        }
+    }
+}
+
+/// OutputFormat enum of the formats of the extracted content
+#[pyclass(eq, eq_int)]
+#[derive(Clone, PartialEq)]
+pub enum OutputFormat {
+    PlainText,
+    Xhtml,
+    Html,
+    BodyXhtml,
+    Json,
+    Markdown,
+}
+
+impl From<OutputFormat> for ecore::OutputFormat {
+    fn from(format: OutputFormat) -> Self {
+        match format {
+            OutputFormat::PlainText => ecore::OutputFormat::PlainText,
+            OutputFormat::Xhtml => ecore::OutputFormat::Xhtml,
+            OutputFormat::Html => ecore::OutputFormat::Html,
+            OutputFormat::BodyXhtml => ecore::OutputFormat::BodyXhtml,
+            OutputFormat::Json => ecore::OutputFormat::Json,
+            OutputFormat::Markdown => ecore::OutputFormat::Markdown,
         }
     }
 }
@@ -88,6 +135,26 @@ impl StreamReader {
             .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
         Ok(bytes_read)
     }
+
+    /// Returns the metadata once the parsing is done as dict, or None until the end of the
+    /// stream is reached
+    pub fn final_metadata<'py>(&self, py: Python<'py>) -> PyResult<Option<PyObject>> {
+        match self.reader.final_metadata() {
+            Some(metadata) => Ok(Some(metadata_hashmap_to_pydict(py, metadata)?.into())),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the rest of the stream, discarding it, and returns the metadata once the parsing is
+    /// done as dict. Raises an IOError if the parsing gave no metadata, as the Rust `finish` does
+    pub fn finish<'py>(&mut self, py: Python<'py>) -> PyResult<PyObject> {
+        std::io::copy(&mut self.reader, &mut std::io::sink())
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyIOError, _>(format!("{}", e)))?;
+        let metadata = self.reader.final_metadata().ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyIOError, _>("No metadata at the end of the stream")
+        })?;
+        Ok(metadata_hashmap_to_pydict(py, metadata)?.into())
+    }
 }
 
 /// `Extractor` is the entry for all extract APIs
@@ -103,13 +170,21 @@ impl Extractor {
         Self(ecore::Extractor::new())
     }
 
-    /// Set the maximum length of the extracted text. Used only for extract_to_string functions
+    /// Set the maximum length of the extracted text, or None for no limit. Used only for
+    /// extract_to_string functions
     /// Default: 500_000
-    pub fn set_extract_string_max_length(&self, max_length: i32) -> Self {
+    #[pyo3(signature = (max_length))]
+    pub fn set_extract_string_max_length(&self, max_length: Option<usize>) -> Self {
         let inner = self.0.clone().set_extract_string_max_length(max_length);
         Self(inner)
     }
 
+    /// Set whether text longer than the maximum length fails instead of being truncated
+    pub fn set_extract_string_strict(&self, strict: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_extract_string_strict(strict);
+        Ok(Self(inner))
+    }
+
     /// Set the encoding to use for when extracting text to a stream.
     /// Not used for extract_to_string functions.
     /// Default: CharSet::UTF_8
@@ -118,6 +193,16 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the encoding of the streams by the Java name of any charset that has no `CharSet`,
+    /// for example `windows-1251`. Unsupported names fail when extracting
+    pub fn set_encoding_name(&self, name: &str) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_encoding(ecore::CharSet::Custom(name.to_string()));
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the PDF parser
     pub fn set_pdf_config(&self, config: PdfParserConfig) -> PyResult<Self> {
         let inner = self.0.clone().set_pdf_config(config.into());
@@ -136,9 +221,79 @@ impl Extractor {
         Ok(Self(inner))
     }
 
+    /// Set the password of encrypted documents, also used for the embedded documents
+    pub fn set_password(&self, password: &str) -> PyResult<Self> {
+        let inner = self.0.clone().set_password(password);
+        Ok(Self(inner))
+    }
+
+    /// Set the candidate passwords of encrypted documents, tried in order for each document
+    pub fn set_passwords(&self, passwords: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_passwords(&passwords);
+        Ok(Self(inner))
+    }
+
+    /// Set the mime types of the documents to parse, e.g. "application/pdf" or "image/*"
+    pub fn set_allowed_mime_types(&self, mime_types: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_allowed_mime_types(&mime_types);
+        Ok(Self(inner))
+    }
+
+    /// Set the mime types of the documents to never parse, e.g. "image/*" or "audio/*"
+    pub fn set_denied_mime_types(&self, mime_types: Vec<String>) -> PyResult<Self> {
+        let inner = self.0.clone().set_denied_mime_types(&mime_types);
+        Ok(Self(inner))
+    }
+
+    /// Set a custom tika-config.xml, used by all the extractions of this extractor.
+    /// Raises a ValueError if the xml is not a valid tika config
+    pub fn set_tika_config_xml(&self, xml: &str) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_tika_config_xml(xml)
+            .map_err(|e| PyErr::new::<PyValueError, _>(format!("{:?}", e)))?;
+        Ok(Self(inner))
+    }
+
+    /// Set a custom tika-config.xml from a file, used by all the extractions of this extractor.
+    /// Raises a FileNotFoundError if the file does not exist and a ValueError if it cannot be
+    /// read or is not a valid tika config
+    pub fn set_tika_config_file(&self, path: &str) -> PyResult<Self> {
+        let inner = self
+            .0
+            .clone()
+            .set_tika_config_file(path)
+            .map_err(|e| match e {
+                ecore::Error::FileNotFound(_) => {
+                    PyErr::new::<pyo3::exceptions::PyFileNotFoundError, _>(format!("{:?}", e))
+                }
+                e => PyErr::new::<PyValueError, _>(format!("{:?}", e)),
+            })?;
+        Ok(Self(inner))
+    }
+
     /// Set the configuration for the parse as xml
     pub fn set_xml_output(&self, xml_output: bool) -> PyResult<Self> {
-        let inner = self.0.clone().set_xml_output(xml_output);
+        let format = if xml_output {
+            ecore::OutputFormat::Xhtml
+        } else {
+            ecore::OutputFormat::PlainText
+        };
+        let inner = self.0.clone().set_output_format(format);
+        Ok(Self(inner))
+    }
+
+    /// Set the format of the extracted content, for both the string and the stream extractions
+    pub fn set_output_format(&self, output_format: OutputFormat) -> PyResult<Self> {
+        let inner = self.0.clone().set_output_format(output_format.into());
+        Ok(Self(inner))
+    }
+
+    /// Set whether to add the languages detected in the extracted content to the metadata of the
+    /// string extractions, under `X-TIKA:detected_language`
+    pub fn set_detect_languages(&self, detect_languages: bool) -> PyResult<Self> {
+        let inner = self.0.clone().set_detect_languages(detect_languages);
         Ok(Self(inner))
     }
 
@@ -168,15 +323,25 @@ impl Extractor {
 
     /// Extracts text from a bytearray. Returns a tuple with stream of the extracted text
     /// the stream is decoded using the extractor's `encoding` and tika metadata.
+    /// The optional file name, content type and charset are hints for the detection.
+    #[pyo3(signature = (buffer, file_name=None, content_type=None, charset=None))]
     pub fn extract_bytes<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
+        file_name: Option<String>,
+        content_type: Option<String>,
+        charset: Option<String>,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, PyObject)> {
-        let slice = buffer.to_vec();
+        let options = ecore::ExtractOptions {
+            file_name,
+            content_type,
+            charset,
+        };
+        // The copied buffer is moved into the reader, so it outlives the background parsing
         let (reader, metadata) = self
             .0
-            .extract_bytes(&slice)
+            .extract_vec_with(buffer.to_vec(), &options)
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
@@ -193,14 +358,24 @@ impl Extractor {
 
     /// Extracts text from a url. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and tika metdata.
+    /// The optional file name, content type and charset are hints for the detection.
+    #[pyo3(signature = (url, file_name=None, content_type=None, charset=None))]
     pub fn extract_url<'py>(
         &self,
         url: &str,
+        file_name: Option<String>,
+        content_type: Option<String>,
+        charset: Option<String>,
         py: Python<'py>,
     ) -> PyResult<(StreamReader, PyObject)> {
+        let options = ecore::ExtractOptions {
+            file_name,
+            content_type,
+            charset,
+        };
         let (reader, metadata) = self
             .0
-            .extract_url(&url)
+            .extract_url_with(url, &options)
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
@@ -228,40 +403,60 @@ impl Extractor {
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
-        Ok((content, py_metadata.into()))
+        Ok((content.into_string(), py_metadata.into()))
     }
 
     /// Extracts text from a bytearray. string that is of maximum length
     /// of the extractor's `extract_string_max_length` and the metadata as dict.
+    /// The optional file name, content type and charset are hints for the detection.
+    #[pyo3(signature = (buffer, file_name=None, content_type=None, charset=None))]
     pub fn extract_bytes_to_string<'py>(
         &self,
         buffer: &Bound<'_, PyByteArray>,
+        file_name: Option<String>,
+        content_type: Option<String>,
+        charset: Option<String>,
         py: Python<'py>,
     ) -> PyResult<(String, PyObject)> {
+        let options = ecore::ExtractOptions {
+            file_name,
+            content_type,
+            charset,
+        };
         let (content, metadata) = self
             .0
-            .extract_bytes_to_string(&buffer.to_vec())
+            .extract_bytes_to_string_with(&buffer.to_vec(), &options)
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         // Create a new `StreamReader` with initial buffer capacity of ecore::DEFAULT_BUF_SIZE bytes
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
-        Ok((content, py_metadata.into()))
+        Ok((content.into_string(), py_metadata.into()))
     }
 
     /// Extracts text from a URL. Returns a tuple with string that is of maximum length
     /// of the extractor's `extract_string_max_length` and the metadata as dict.
+    /// The optional file name, content type and charset are hints for the detection.
+    #[pyo3(signature = (url, file_name=None, content_type=None, charset=None))]
     pub fn extract_url_to_string<'py>(
         &self,
         url: &str,
+        file_name: Option<String>,
+        content_type: Option<String>,
+        charset: Option<String>,
         py: Python<'py>,
     ) -> PyResult<(String, PyObject)> {
+        let options = ecore::ExtractOptions {
+            file_name,
+            content_type,
+            charset,
+        };
         let (content, metadata) = self
             .0
-            .extract_url_to_string(url)
+            .extract_url_to_string_with(url, &options)
             .map_err(|e| PyErr::new::<PyTypeError, _>(format!("{:?}", e)))?;
 
         let py_metadata = metadata_hashmap_to_pydict(py, &metadata)?;
-        Ok((content, py_metadata.into()))
+        Ok((content.into_string(), py_metadata.into()))
     }
 
     fn __repr__(&self) -> String {
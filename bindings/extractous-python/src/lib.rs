@@ -54,6 +54,7 @@ pub use config::*;
 #[pymodule]
 fn _extractous(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<CharSet>()?;
+    m.add_class::<OutputFormat>()?;
     m.add_class::<StreamReader>()?;
     m.add_class::<Extractor>()?;
 
@@ -1,4 +1,6 @@
-from extractous import Extractor
+import json
+
+from extractous import Extractor, OutputFormat
 from utils import read_to_string, extract_body_text
 
 
@@ -58,3 +60,24 @@ def test_extract_bytes_as_xml():
     print(f"test_pdf:test_extract_bytes_as_xml result = {result_xml}")
     result_text = extract_body_text(result_xml)
     assert result_text.strip() == expected_result().strip()
+
+def test_extract_file_as_json():
+    extractor = Extractor()
+    extractor = extractor.set_output_format(OutputFormat.Json)
+    reader, metadata = extractor.extract_file("tests/quarkus.pdf")
+
+    result = json.loads(read_to_string(reader))
+
+    print(f"test_pdf:test_extract_file_as_json result = {result}")
+    assert result["content"] == expected_result()
+    assert result["metadata"]["Content-Type"] == metadata["Content-Type"]
+
+def test_extract_file_final_metadata():
+    extractor = Extractor()
+    reader, metadata = extractor.extract_file("tests/quarkus.pdf")
+    assert reader.final_metadata() is None
+
+    final_metadata = reader.finish()
+
+    assert "xmpTPg:NPages" in final_metadata
+    assert reader.final_metadata() == final_metadata
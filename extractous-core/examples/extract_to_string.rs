@@ -1,4 +1,4 @@
-use extractous::Extractor;
+use extractous::{Extractor, OutputFormat};
 
 fn main() {
     // Get the command-line arguments
@@ -6,7 +6,7 @@ fn main() {
     let file_path = &args[1];
 
     // Extract the provided file content to a string
-    let extractor = Extractor::new().set_xml_output(true);
+    let extractor = Extractor::new().set_output_format(OutputFormat::Xhtml);
     let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
     println!("{}", content);
 }
@@ -0,0 +1,361 @@
+use crate::blocking_pool::BlockingPool;
+use crate::errors::{Error, ExtractResult};
+use crate::{ExtractOptions, ExtractedText, Extractor, Metadata, StreamReader, DEFAULT_BUF_SIZE};
+use std::future::Future;
+use std::io::Read;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+use tokio::sync::oneshot;
+
+/// Async counterpart of the [`Extractor`], available with the `async` feature.
+///
+/// All the JNI and Tika work is done on a dedicated pool of blocking threads, so the futures never
+/// block the executor. The pool is shared by the extractor clones and the readers they return,
+/// and the number of extractions running at the same time is bounded by `max_concurrency`.
+/// ```no_run
+/// use extractous::{AsyncExtractor, Extractor};
+/// use tokio::io::AsyncReadExt;
+///
+/// # async fn example() {
+/// let extractor = AsyncExtractor::new(Extractor::new()).set_max_concurrency(4);
+/// let (mut reader, metadata) = extractor.extract_file("README.md").await.unwrap();
+///
+/// let mut content = String::new();
+/// reader.read_to_string(&mut content).await.unwrap();
+/// println!("{}", content);
+/// # }
+/// ```
+///
+#[derive(Clone)]
+pub struct AsyncExtractor {
+    extractor: Arc<Extractor>,
+    pool: Arc<BlockingPool>,
+}
+
+impl Default for AsyncExtractor {
+    fn default() -> Self {
+        Self::new(Extractor::default())
+    }
+}
+
+impl From<Extractor> for AsyncExtractor {
+    fn from(extractor: Extractor) -> Self {
+        Self::new(extractor)
+    }
+}
+
+impl AsyncExtractor {
+    /// Creates an async extractor that uses the configuration of the given `extractor`.
+    /// The blocking pool is bounded to the available parallelism by default
+    pub fn new(extractor: Extractor) -> Self {
+        let max_concurrency = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+        Self {
+            extractor: Arc::new(extractor),
+            pool: Arc::new(BlockingPool::new(max_concurrency)),
+        }
+    }
+
+    /// Set the maximum number of blocking extraction calls running at the same time. Calls beyond
+    /// this limit wait in a queue. This also bounds the number of threads of the blocking pool.
+    /// Values lower than 1 are treated as 1.
+    /// Default: the available parallelism
+    pub fn set_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.pool = Arc::new(BlockingPool::new(max_concurrency));
+        self
+    }
+
+    /// Returns the maximum number of blocking extraction calls running at the same time
+    pub fn max_concurrency(&self) -> usize {
+        self.pool.max_threads()
+    }
+
+    /// Returns the configuration used for the extractions
+    pub fn extractor(&self) -> &Extractor {
+        &self.extractor
+    }
+
+    /// Runs `f` with the extractor on the blocking pool
+    async fn run<T, F>(&self, f: F) -> ExtractResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&Extractor) -> ExtractResult<T> + Send + 'static,
+    {
+        let extractor = Arc::clone(&self.extractor);
+        let (tx, rx) = oneshot::channel();
+        self.pool.submit(move || {
+            // The receiver is gone when the future is dropped; the result is then dropped here
+            let _ = tx.send(f(&extractor));
+        });
+
+        rx.await
+            .map_err(|_| Error::Unknown("Blocking extraction task panicked".to_string()))?
+    }
+
+    fn stream_reader(&self, reader: StreamReader) -> AsyncStreamReader {
+        AsyncStreamReader::new(reader, Arc::clone(&self.pool))
+    }
+
+    /// Extracts text from a file path. Returns a tuple with stream of the extracted text and metadata.
+    /// the stream is decoded using the extractor's `encoding`
+    pub async fn extract_file(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let file_path = file_path.to_string();
+        let (reader, metadata) = self.run(move |e| e.extract_file(&file_path)).await?;
+        Ok((self.stream_reader(reader), metadata))
+    }
+
+    /// Extracts text from a byte buffer. Returns a tuple with stream of the extracted text and metadata.
+    /// the stream is decoded using the extractor's `encoding`.
+    ///
+    /// The buffer is copied, use [`AsyncExtractor::extract_vec`] to avoid the copy.
+    pub async fn extract_bytes(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        self.extract_vec(buffer.to_vec()).await
+    }
+
+    /// Same as [`AsyncExtractor::extract_bytes`] with the document hints of `options`
+    pub async fn extract_bytes_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        self.extract_vec_with(buffer.to_vec(), options).await
+    }
+
+    /// Extracts text from an owned byte buffer. Returns a tuple with stream of the extracted text
+    /// and metadata. the stream is decoded using the extractor's `encoding`
+    pub async fn extract_vec(
+        &self,
+        buffer: Vec<u8>,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let (reader, metadata) = self.run(move |e| e.extract_vec(buffer)).await?;
+        Ok((self.stream_reader(reader), metadata))
+    }
+
+    /// Same as [`AsyncExtractor::extract_vec`] with the document hints of `options`
+    pub async fn extract_vec_with(
+        &self,
+        buffer: Vec<u8>,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let options = options.clone();
+        let (reader, metadata) = self
+            .run(move |e| e.extract_vec_with(buffer, &options))
+            .await?;
+        Ok((self.stream_reader(reader), metadata))
+    }
+
+    /// Extracts text from an url. Returns a tuple with stream of the extracted text and metadata.
+    /// the stream is decoded using the extractor's `encoding`
+    pub async fn extract_url(&self, url: &str) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let url = url.to_string();
+        let (reader, metadata) = self.run(move |e| e.extract_url(&url)).await?;
+        Ok((self.stream_reader(reader), metadata))
+    }
+
+    /// Same as [`AsyncExtractor::extract_url`] with the document hints of `options`
+    pub async fn extract_url_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(AsyncStreamReader, Metadata)> {
+        let url = url.to_string();
+        let options = options.clone();
+        let (reader, metadata) = self
+            .run(move |e| e.extract_url_with(&url, &options))
+            .await?;
+        Ok((self.stream_reader(reader), metadata))
+    }
+
+    /// Extracts text from a file path. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub async fn extract_file_to_string(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        let file_path = file_path.to_string();
+        self.run(move |e| e.extract_file_to_string(&file_path))
+            .await
+    }
+
+    /// Extracts text from a byte buffer. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub async fn extract_bytes_to_string(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        let buffer = buffer.to_vec();
+        self.run(move |e| e.extract_bytes_to_string(&buffer)).await
+    }
+
+    /// Same as [`AsyncExtractor::extract_bytes_to_string`] with the document hints of `options`
+    pub async fn extract_bytes_to_string_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        let buffer = buffer.to_vec();
+        let options = options.clone();
+        self.run(move |e| e.extract_bytes_to_string_with(&buffer, &options))
+            .await
+    }
+
+    /// Extracts text from a URL. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub async fn extract_url_to_string(
+        &self,
+        url: &str,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        let url = url.to_string();
+        self.run(move |e| e.extract_url_to_string(&url)).await
+    }
+
+    /// Same as [`AsyncExtractor::extract_url_to_string`] with the document hints of `options`
+    pub async fn extract_url_to_string_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        let url = url.to_string();
+        let options = options.clone();
+        self.run(move |e| e.extract_url_to_string_with(&url, &options))
+            .await
+    }
+}
+
+/// AsyncStreamReader implements tokio::io::AsyncRead
+///
+/// Each read of the underlying [`StreamReader`] is done on the blocking pool of the
+/// [`AsyncExtractor`] that created it, in chunks of [`DEFAULT_BUF_SIZE`] bytes.
+pub struct AsyncStreamReader {
+    pool: Arc<BlockingPool>,
+    state: State,
+}
+
+/// A stream reader with the last chunk read from it
+struct Chunk {
+    reader: StreamReader,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+enum State {
+    Idle(Box<Chunk>),
+    Busy(oneshot::Receiver<(Box<Chunk>, std::io::Result<usize>)>),
+    /// The reader was lost in a panicking read
+    Failed,
+}
+
+impl AsyncStreamReader {
+    fn new(reader: StreamReader, pool: Arc<BlockingPool>) -> Self {
+        Self {
+            pool,
+            state: State::Idle(Box::new(Chunk {
+                reader,
+                buf: Vec::with_capacity(DEFAULT_BUF_SIZE),
+                pos: 0,
+            })),
+        }
+    }
+
+    /// Returns the metadata once the parsing is done, see [`StreamReader::final_metadata`].
+    /// `None` until the end of the stream is reached
+    pub fn final_metadata(&self) -> Option<&Metadata> {
+        match &self.state {
+            State::Idle(chunk) => chunk.reader.final_metadata(),
+            _ => None,
+        }
+    }
+
+    /// Reads the rest of the stream on the blocking pool, discarding it, and returns the metadata
+    /// once the parsing is done, see [`StreamReader::finish`]
+    pub async fn finish(mut self) -> ExtractResult<Metadata> {
+        let panicked = || Error::Unknown("Blocking read task panicked".to_string());
+        let chunk = match std::mem::replace(&mut self.state, State::Failed) {
+            State::Idle(chunk) => chunk,
+            State::Busy(rx) => {
+                let (chunk, result) = rx.await.map_err(|_| panicked())?;
+                result?;
+                chunk
+            }
+            State::Failed => return Err(panicked()),
+        };
+
+        let (tx, rx) = oneshot::channel();
+        self.pool.submit(move || {
+            let _ = tx.send(chunk.reader.finish());
+        });
+        rx.await.map_err(|_| panicked())?
+    }
+}
+
+impl AsyncRead for AsyncStreamReader {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        dst: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+        loop {
+            match &mut this.state {
+                State::Idle(chunk) => {
+                    // Serve what is left of the last chunk first
+                    if chunk.pos < chunk.buf.len() {
+                        let n = dst.remaining().min(chunk.buf.len() - chunk.pos);
+                        dst.put_slice(&chunk.buf[chunk.pos..chunk.pos + n]);
+                        chunk.pos += n;
+                        return Poll::Ready(Ok(()));
+                    }
+
+                    let State::Idle(mut chunk) = std::mem::replace(&mut this.state, State::Failed)
+                    else {
+                        unreachable!()
+                    };
+                    let (tx, rx) = oneshot::channel();
+                    this.pool.submit(move || {
+                        chunk.buf.resize(DEFAULT_BUF_SIZE, 0);
+                        let result = chunk.reader.read(&mut chunk.buf);
+                        chunk.buf.truncate(*result.as_ref().unwrap_or(&0));
+                        chunk.pos = 0;
+                        let _ = tx.send((chunk, result));
+                    });
+                    this.state = State::Busy(rx);
+                }
+                State::Busy(rx) => {
+                    let received = ready!(Pin::new(rx).poll(cx));
+                    let Ok((chunk, result)) = received else {
+                        this.state = State::Failed;
+                        continue;
+                    };
+                    this.state = State::Idle(chunk);
+                    match result {
+                        // End of the stream, nothing is put into dst
+                        Ok(0) => return Poll::Ready(Ok(())),
+                        Ok(_) => continue,
+                        Err(e) => return Poll::Ready(Err(e)),
+                    }
+                }
+                State::Failed => {
+                    return Poll::Ready(Err(std::io::Error::other("Blocking read task panicked")))
+                }
+            }
+        }
+    }
+}
+
+impl Drop for AsyncStreamReader {
+    fn drop(&mut self) {
+        // Closing the stream reader is a JNI call too, keep it off the executor thread
+        if let State::Idle(chunk) = std::mem::replace(&mut self.state, State::Failed) {
+            self.pool.submit(move || drop(chunk));
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::blocking_pool::BlockingPool;
+use crate::errors::{Error, ExtractResult};
+use crate::{ExtractedText, Extractor, Metadata};
+use std::collections::BTreeMap;
+use std::panic::AssertUnwindSafe;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// A source of a batch extraction, see [`Extractor::extract_many`]
+#[derive(Debug, Clone)]
+pub enum BatchSource {
+    /// A file path
+    File(String),
+    /// An url
+    Url(String),
+    /// A byte buffer, identified by `id` in the batch results
+    Bytes { id: String, buffer: Vec<u8> },
+}
+
+impl BatchSource {
+    /// Returns the identifier of the source: the file path, the url or the buffer id
+    pub fn id(&self) -> &str {
+        match self {
+            BatchSource::File(file_path) => file_path,
+            BatchSource::Url(url) => url,
+            BatchSource::Bytes { id, .. } => id,
+        }
+    }
+
+    fn extract(&self, extractor: &Extractor) -> ExtractResult<(ExtractedText, Metadata)> {
+        match self {
+            BatchSource::File(file_path) => extractor.extract_file_to_string(file_path),
+            BatchSource::Url(url) => extractor.extract_url_to_string(url),
+            BatchSource::Bytes { buffer, .. } => extractor.extract_bytes_to_string(buffer),
+        }
+    }
+}
+
+impl From<&str> for BatchSource {
+    fn from(file_path: &str) -> Self {
+        BatchSource::File(file_path.to_string())
+    }
+}
+
+impl From<String> for BatchSource {
+    fn from(file_path: String) -> Self {
+        BatchSource::File(file_path)
+    }
+}
+
+impl From<&Path> for BatchSource {
+    fn from(file_path: &Path) -> Self {
+        BatchSource::File(file_path.to_string_lossy().into_owned())
+    }
+}
+
+impl From<PathBuf> for BatchSource {
+    fn from(file_path: PathBuf) -> Self {
+        BatchSource::from(file_path.as_path())
+    }
+}
+
+/// The order in which the results of a batch extraction are returned
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchOrder {
+    /// Results are returned as soon as they are ready
+    Completion,
+    /// Results are returned in the order of the sources
+    Input,
+}
+
+/// The result of extracting one source of a batch
+#[derive(Debug)]
+pub struct BatchItem {
+    /// The position of the source in the batch, starting from 0
+    pub index: usize,
+    /// The identifier of the source, see [`BatchSource::id`]
+    pub source: String,
+    /// How long the extraction took, not counting the time spent waiting in the queue
+    pub duration: Duration,
+    /// The extracted text, of maximum length of the extractor's `extract_string_max_length`,
+    /// and metadata. Or the error that made this extraction fail
+    pub result: ExtractResult<(ExtractedText, Metadata)>,
+}
+
+/// Iterator over the results of [`Extractor::extract_many`]
+///
+/// Sources are pulled from the input iterator only when there is room for them, so at most
+/// `max_concurrency` extractions run at the same time and large batches are never collected
+/// in memory. Dropping the iterator stops the batch once the running extractions finish.
+pub struct BatchIter<I> {
+    sources: I,
+    extractor: Arc<Extractor>,
+    pool: BlockingPool,
+    order: BatchOrder,
+    tx: Sender<BatchItem>,
+    rx: Receiver<BatchItem>,
+    num_submitted: usize,
+    num_running: usize,
+    /// Finished items waiting for their turn, used only for [`BatchOrder::Input`]
+    ready: BTreeMap<usize, BatchItem>,
+    next_index: usize,
+}
+
+impl<I> BatchIter<I>
+where
+    I: Iterator,
+    I::Item: Into<BatchSource>,
+{
+    pub(crate) fn new(
+        extractor: &Extractor,
+        sources: I,
+        max_concurrency: usize,
+        order: BatchOrder,
+    ) -> Self {
+        let (tx, rx) = channel();
+        Self {
+            sources,
+            extractor: Arc::new(extractor.clone()),
+            pool: BlockingPool::new(max_concurrency),
+            order,
+            tx,
+            rx,
+            num_submitted: 0,
+            num_running: 0,
+            ready: BTreeMap::new(),
+            next_index: 0,
+        }
+    }
+
+    /// Returns the number of sources pulled from the input so far
+    pub fn num_submitted(&self) -> usize {
+        self.num_submitted
+    }
+
+    /// Submits sources until the concurrency limit is reached or the input is exhausted
+    fn fill(&mut self) {
+        let max_concurrency = self.pool.max_threads();
+        // Finished items waiting for a slow one count too, which bounds the memory held for the
+        // input order
+        while self.num_running + self.ready.len() < max_concurrency {
+            let Some(source) = self.sources.next() else {
+                return;
+            };
+            let source: BatchSource = source.into();
+            let index = self.num_submitted;
+            let extractor = Arc::clone(&self.extractor);
+            let tx = self.tx.clone();
+
+            self.pool.submit(move || {
+                let start = Instant::now();
+                let result =
+                    std::panic::catch_unwind(AssertUnwindSafe(|| source.extract(&extractor)))
+                        .unwrap_or_else(|_| Err(Error::Unknown("Extraction panicked".to_string())));
+
+                // The receiver is gone only if the iterator was dropped
+                let _ = tx.send(BatchItem {
+                    index,
+                    source: source.id().to_string(),
+                    duration: start.elapsed(),
+                    result,
+                });
+            });
+            self.num_submitted += 1;
+            self.num_running += 1;
+        }
+    }
+
+    /// Waits for the next finished item
+    fn recv(&mut self) -> Option<BatchItem> {
+        if self.num_running == 0 {
+            return None;
+        }
+        // Every submitted job sends exactly one item, and self.tx keeps the channel open
+        let item = self.rx.recv().ok()?;
+        self.num_running -= 1;
+        Some(item)
+    }
+}
+
+impl<I> Iterator for BatchIter<I>
+where
+    I: Iterator,
+    I::Item: Into<BatchSource>,
+{
+    type Item = BatchItem;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.ready.remove(&self.next_index) {
+                self.next_index += 1;
+                return Some(item);
+            }
+
+            self.fill();
+            let item = self.recv()?;
+            match self.order {
+                BatchOrder::Completion => return Some(item),
+                BatchOrder::Input => {
+                    self.ready.insert(item.index, item);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+use std::panic::AssertUnwindSafe;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// A pool of threads dedicated to blocking extraction work.
+///
+/// Threads are spawned on demand, up to `max_threads`, and stay alive until the pool is dropped.
+/// Jobs submitted while all threads are busy wait in a queue, which bounds the number of
+/// concurrent JNI calls to `max_threads`.
+pub(crate) struct BlockingPool {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    max_threads: usize,
+    state: Mutex<PoolState>,
+    job_available: Condvar,
+}
+
+struct PoolState {
+    queue: VecDeque<Job>,
+    num_threads: usize,
+    num_idle: usize,
+    shutdown: bool,
+}
+
+impl BlockingPool {
+    pub(crate) fn new(max_threads: usize) -> Self {
+        Self {
+            shared: Arc::new(Shared {
+                max_threads: max_threads.max(1),
+                state: Mutex::new(PoolState {
+                    queue: VecDeque::new(),
+                    num_threads: 0,
+                    num_idle: 0,
+                    shutdown: false,
+                }),
+                job_available: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of jobs that run at the same time
+    pub(crate) fn max_threads(&self) -> usize {
+        self.shared.max_threads
+    }
+
+    /// Queues a job to run on one of the pool threads
+    pub(crate) fn submit<F>(&self, job: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let mut state = self.shared.lock_state();
+        state.queue.push_back(Box::new(job));
+
+        // Spawn a new thread only when the idle ones can't take all the queued jobs
+        if state.queue.len() > state.num_idle && state.num_threads < self.shared.max_threads {
+            let shared = Arc::clone(&self.shared);
+            let spawned = thread::Builder::new()
+                .name(format!("extractous-blocking-{}", state.num_threads))
+                .spawn(move || shared.run_worker());
+            // If the thread cannot be spawned, the job waits for one of the running threads.
+            if spawned.is_ok() {
+                state.num_threads += 1;
+            }
+        }
+        self.shared.job_available.notify_one();
+    }
+}
+
+impl Drop for BlockingPool {
+    fn drop(&mut self) {
+        // Threads finish the queued jobs before exiting
+        self.shared.lock_state().shutdown = true;
+        self.shared.job_available.notify_all();
+    }
+}
+
+impl Shared {
+    fn lock_state(&self) -> std::sync::MutexGuard<'_, PoolState> {
+        // Jobs never run while the lock is held, so a poisoned state is still consistent
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn run_worker(&self) {
+        let mut state = self.lock_state();
+        loop {
+            if let Some(job) = state.queue.pop_front() {
+                drop(state);
+                // A panicking job must not take the thread down with it. The job's result
+                // channel is dropped, which is how the caller learns about the panic
+                let _ = std::panic::catch_unwind(AssertUnwindSafe(job));
+                state = self.lock_state();
+                continue;
+            }
+            if state.shutdown {
+                state.num_threads -= 1;
+                return;
+            }
+
+            state.num_idle += 1;
+            state = self
+                .job_available
+                .wait(state)
+                .unwrap_or_else(|e| e.into_inner());
+            state.num_idle -= 1;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use crate::tika;
+use jni::objects::GlobalRef;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+/// Handle to abort in-flight extractions from another thread.
+///
+/// Set it on an extractor with [`crate::Extractor::set_cancellation_token`]. Cancelling the token
+/// aborts every extraction running with it: `*_to_string` calls return [`crate::Error::Cancelled`]
+/// and active streams fail their next read with it. Extractions started after the token is cancelled
+/// are aborted as soon as they start, so use a new token for the next extractions.
+/// ```no_run
+/// use extractous::{CancellationToken, Extractor};
+///
+/// let token = CancellationToken::new();
+/// let extractor = Extractor::new().set_cancellation_token(token.clone());
+///
+/// let handle = std::thread::spawn(move || extractor.extract_file_to_string("large.pdf"));
+/// token.cancel();
+/// let result = handle.join().unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    state: Arc<Mutex<TokenState>>,
+}
+
+#[derive(Debug, Default)]
+struct TokenState {
+    cancelled: bool,
+    next_id: u64,
+    /// The java `ai.yobix.ParseMonitor` of each running extraction
+    monitors: HashMap<u64, GlobalRef>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Aborts all the extractions running with this token and the ones started later
+    pub fn cancel(&self) {
+        let monitors = {
+            let mut state = self.lock_state();
+            state.cancelled = true;
+            std::mem::take(&mut state.monitors)
+        };
+        // The lock is released, the java calls might take a moment
+        for monitor in monitors.values() {
+            tika::cancel_parse_monitor(monitor);
+        }
+    }
+
+    /// Returns true if the token was cancelled
+    pub fn is_cancelled(&self) -> bool {
+        self.lock_state().cancelled
+    }
+
+    /// Registers the monitor of an extraction, until the returned registration is dropped. If the
+    /// token is already cancelled the monitor is cancelled right away, so the extraction fails
+    /// like the ones cancelled while running, with the path and the java exception
+    pub(crate) fn register(&self, monitor: &GlobalRef) -> Registration {
+        let (id, cancelled) = {
+            let mut state = self.lock_state();
+            let id = state.next_id;
+            state.next_id += 1;
+            if !state.cancelled {
+                state.monitors.insert(id, monitor.clone());
+            }
+            (id, state.cancelled)
+        };
+        if cancelled {
+            tika::cancel_parse_monitor(monitor);
+        }
+        Registration {
+            token: self.clone(),
+            id,
+        }
+    }
+
+    fn lock_state(&self) -> MutexGuard<'_, TokenState> {
+        // The state is always consistent, even if a thread panicked while holding the lock
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Keeps the monitor of an extraction registered with a [`CancellationToken`]
+pub(crate) struct Registration {
+    token: CancellationToken,
+    id: u64,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        self.token.lock_state().monitors.remove(&self.id);
+    }
+}
+
+/// Time limit and cancellation of the extractions, passed down to the parse calls
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ParseControl {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) cancellation_token: Option<CancellationToken>,
+}
+
+impl ParseControl {
+    /// Returns true if the extractions are neither time limited nor cancellable
+    pub(crate) fn is_unbounded(&self) -> bool {
+        self.timeout.is_none() && self.cancellation_token.is_none()
+    }
+}
@@ -0,0 +1,458 @@
+use crate::errors::{Error, ExtractResult};
+use crate::Page;
+use std::io::Read;
+
+/// Unit of the size of the chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum ChunkUnit {
+    /// Unicode characters
+    #[default]
+    Chars,
+    /// Bytes of the UTF-8 text. Chunks never split a character
+    Bytes,
+}
+
+/// A chunk of extracted text, see [`Chunker`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    /// The text of the chunk, without surrounding whitespace
+    pub text: String,
+    /// The offset of the first character of the chunk in the source text, in characters. For
+    /// chunks of pages, it is the offset in the text of the page
+    pub start: usize,
+    /// The offset after the last character of the chunk in the source text, in characters
+    pub end: usize,
+    /// The number of the page holding the chunk, when the pages were chunked
+    pub page_number: Option<usize>,
+}
+
+/// Splits extracted text into chunks of a maximum size, for example to compute embeddings.
+///
+/// Chunks end at the last paragraph break that fits, then at the last sentence end and then at
+/// the last word break. Paragraph and sentence breaks are only used when they keep at least half
+/// of the maximum size, so that chunks are not much shorter than needed. A word longer than the
+/// maximum size is split.
+/// ```rust
+/// use extractous::{Chunker, Extractor};
+///
+/// let (text, _metadata) = Extractor::new().extract_file_to_string("README.md").unwrap();
+/// let chunker = Chunker::new().set_max_size(500).set_overlap(50);
+/// for chunk in chunker.chunk_str(&text) {
+///     println!("{}..{}: {}", chunk.start, chunk.end, chunk.text);
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunker {
+    max_size: usize,
+    unit: ChunkUnit,
+    overlap: usize,
+}
+
+impl Default for Chunker {
+    fn default() -> Self {
+        Self {
+            max_size: 1000,
+            unit: ChunkUnit::Chars,
+            overlap: 0,
+        }
+    }
+}
+
+impl Chunker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum size of the chunks, at least 1.
+    /// Default: 1000
+    pub fn set_max_size(mut self, max_size: usize) -> Self {
+        self.max_size = max_size.max(1);
+        self
+    }
+
+    /// Set the unit of the maximum size and of the overlap.
+    /// Default: ChunkUnit::Chars
+    pub fn set_unit(mut self, unit: ChunkUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    /// Set the size of the text repeated at the start of a chunk from the end of the previous one,
+    /// at most half of the maximum size. The overlap starts at a word break when there is one.
+    /// Default: 0
+    pub fn set_overlap(mut self, overlap: usize) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Splits a string into chunks
+    pub fn chunk_str(&self, text: &str) -> Vec<Chunk> {
+        let mut splitter = Splitter::new(self.clone(), text.to_string(), None);
+        std::iter::from_fn(|| splitter.next_chunk(true)).collect()
+    }
+
+    /// Splits pages into chunks. Chunks do not span several pages, and their offsets are in the
+    /// text of their page
+    pub fn chunk_pages<I: IntoIterator<Item = Page>>(&self, pages: I) -> Vec<Chunk> {
+        let mut chunks = Vec::new();
+        for page in pages {
+            let mut splitter = Splitter::new(self.clone(), page.text, Some(page.number));
+            chunks.extend(std::iter::from_fn(|| splitter.next_chunk(true)));
+        }
+        chunks
+    }
+
+    /// Splits a stream of UTF-8 text, for example a [`crate::StreamReader`], into chunks. The
+    /// stream is read as the chunks are consumed, so the whole text is never held in memory
+    pub fn chunk_reader<R: Read>(&self, reader: R) -> ChunkIter<R> {
+        ChunkIter {
+            reader,
+            splitter: Splitter::new(self.clone(), String::new(), None),
+            pending: Vec::new(),
+            eof: false,
+        }
+    }
+
+    fn overlap(&self) -> usize {
+        self.overlap.min(self.max_size / 2)
+    }
+
+    /// Returns the end of the largest prefix of the text fitting in a chunk, or None if the whole
+    /// text fits
+    fn window_end(&self, text: &str) -> Option<usize> {
+        match self.unit {
+            ChunkUnit::Chars => text.char_indices().nth(self.max_size).map(|(i, _)| i),
+            ChunkUnit::Bytes if text.len() <= self.max_size => None,
+            ChunkUnit::Bytes => {
+                let mut end = self.max_size;
+                while !text.is_char_boundary(end) {
+                    end -= 1;
+                }
+                // A chunk holds at least one character
+                Some(end.max(text.chars().next().map_or(0, char::len_utf8)))
+            }
+        }
+    }
+
+    /// Returns the start of the overlap at the end of the chunk
+    fn overlap_start(&self, chunk: &str) -> usize {
+        let overlap = self.overlap();
+        if overlap == 0 {
+            return chunk.len();
+        }
+        let mut start = match self.unit {
+            ChunkUnit::Chars => {
+                let len = chunk.chars().count();
+                chunk
+                    .char_indices()
+                    .nth(len.saturating_sub(overlap))
+                    .map_or(chunk.len(), |(i, _)| i)
+            }
+            ChunkUnit::Bytes => chunk.len().saturating_sub(overlap),
+        };
+        while !chunk.is_char_boundary(start) {
+            start += 1;
+        }
+        // Start the overlap at the next word
+        match chunk[start..].find(char::is_whitespace) {
+            Some(i) if start > 0 && !chunk[..start].ends_with(char::is_whitespace) => start + i,
+            _ => start,
+        }
+    }
+}
+
+/// Returns the length of the chunk taken from the start of the window
+fn cut(window: &str) -> usize {
+    let min = window.len() / 2;
+    if let Some(i) = window.rfind("\n\n").filter(|i| *i >= min) {
+        return i + 2;
+    }
+    if let Some(i) = sentence_end(window).filter(|i| *i >= min) {
+        return i;
+    }
+    match window.char_indices().rev().find(|(_, c)| c.is_whitespace()) {
+        Some((i, c)) if i > 0 => i + c.len_utf8(),
+        _ => window.len(),
+    }
+}
+
+/// Returns the end of the last sentence of the text
+fn sentence_end(text: &str) -> Option<usize> {
+    let mut next = None;
+    for (i, c) in text.char_indices().rev() {
+        let end = i + c.len_utf8();
+        match c {
+            '。' | '！' | '？' => return Some(end),
+            '.' | '!' | '?' if next.is_some_and(char::is_whitespace) => return Some(end),
+            _ => {}
+        }
+        next = Some(c);
+    }
+    None
+}
+
+/// Splits a buffer of text into chunks, the text can be appended while the chunks are taken
+struct Splitter {
+    chunker: Chunker,
+    buffer: String,
+    /// Start of the next chunk in the buffer
+    start: usize,
+    /// Offset of the start of the next chunk in the source, in characters
+    start_offset: usize,
+    page_number: Option<usize>,
+    done: bool,
+}
+
+impl Splitter {
+    fn new(chunker: Chunker, text: String, page_number: Option<usize>) -> Self {
+        Self {
+            chunker,
+            buffer: text,
+            start: 0,
+            start_offset: 0,
+            page_number,
+            done: false,
+        }
+    }
+
+    fn push_str(&mut self, text: &str) {
+        // Drop the chunked text once it is most of the buffer, to keep the copies linear
+        if self.start > self.buffer.len() / 2 {
+            self.buffer.drain(..self.start);
+            self.start = 0;
+        }
+        self.buffer.push_str(text);
+    }
+
+    /// Returns the next chunk. `complete` tells whether the buffer holds the end of the text,
+    /// otherwise None is returned until the buffer holds a full chunk
+    fn next_chunk(&mut self, complete: bool) -> Option<Chunk> {
+        loop {
+            if self.done {
+                return None;
+            }
+            let rest = &self.buffer[self.start..];
+            let len = match self.chunker.window_end(rest) {
+                Some(end) => cut(&rest[..end]),
+                None if complete => {
+                    self.done = true;
+                    rest.len()
+                }
+                None => return None,
+            };
+            let chunk = &rest[..len];
+            let next = if self.done {
+                len
+            } else {
+                self.chunker.overlap_start(chunk).max(1)
+            };
+
+            // Whitespace only chunks are skipped
+            let text = chunk.trim();
+            let result = (!text.is_empty()).then(|| {
+                let leading = chunk[..chunk.len() - chunk.trim_start().len()]
+                    .chars()
+                    .count();
+                let start = self.start_offset + leading;
+                Chunk {
+                    text: text.to_string(),
+                    start,
+                    end: start + text.chars().count(),
+                    page_number: self.page_number,
+                }
+            });
+
+            let mut next = next.min(len);
+            while !chunk.is_char_boundary(next) {
+                next += 1;
+            }
+            self.start_offset += chunk[..next].chars().count();
+            self.start += next;
+            if result.is_some() {
+                return result;
+            }
+        }
+    }
+}
+
+/// Iterator over the chunks of a stream, returned by [`Chunker::chunk_reader`]
+pub struct ChunkIter<R: Read> {
+    reader: R,
+    splitter: Splitter,
+    /// Bytes of an incomplete UTF-8 character at the end of the last read
+    pending: Vec<u8>,
+    eof: bool,
+}
+
+impl<R: Read> ChunkIter<R> {
+    /// Reads the next part of the stream into the splitter
+    fn fill(&mut self) -> ExtractResult<()> {
+        let mut buf = [0u8; 8192];
+        let len = self.reader.read(&mut buf)?;
+        if len == 0 {
+            self.eof = true;
+            if !self.pending.is_empty() {
+                return std::str::from_utf8(&self.pending)
+                    .map(|_| ())
+                    .map_err(Error::Utf8Error);
+            }
+            return Ok(());
+        }
+
+        self.pending.extend_from_slice(&buf[..len]);
+        let valid = match std::str::from_utf8(&self.pending) {
+            Ok(text) => text.len(),
+            // The end of the buffer is the start of a character
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => return Err(Error::Utf8Error(e)),
+        };
+        let text = std::str::from_utf8(&self.pending[..valid])?;
+        self.splitter.push_str(text);
+        self.pending.drain(..valid);
+        Ok(())
+    }
+}
+
+impl<R: Read> Iterator for ChunkIter<R> {
+    type Item = ExtractResult<Chunk>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(chunk) = self.splitter.next_chunk(self.eof) {
+                return Some(Ok(chunk));
+            }
+            if self.eof {
+                return None;
+            }
+            if let Err(e) = self.fill() {
+                // Stop after an error
+                self.eof = true;
+                self.splitter.done = true;
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "First paragraph. It has two sentences.\n\nSecond paragraph is longer. \
+                        It has three sentences! Is this the last one? No.";
+
+    fn texts(chunks: &[Chunk]) -> Vec<&str> {
+        chunks.iter().map(|chunk| chunk.text.as_str()).collect()
+    }
+
+    #[test]
+    fn boundaries_test() {
+        let chunks = Chunker::new().set_max_size(60).chunk_str(TEXT);
+        assert_eq!(
+            texts(&chunks),
+            vec![
+                "First paragraph. It has two sentences.",
+                "Second paragraph is longer. It has three sentences!",
+                "Is this the last one? No.",
+            ]
+        );
+
+        // The offsets are in characters of the source
+        for chunk in &chunks {
+            let source: String = TEXT
+                .chars()
+                .skip(chunk.start)
+                .take(chunk.end - chunk.start)
+                .collect();
+            assert_eq!(source, chunk.text);
+        }
+    }
+
+    #[test]
+    fn word_boundaries_test() {
+        let chunks = Chunker::new()
+            .set_max_size(10)
+            .chunk_str("one two three four");
+        assert_eq!(texts(&chunks), vec!["one two", "three four"]);
+
+        let chunks = Chunker::new().set_max_size(4).chunk_str("abcdefghij");
+        assert_eq!(texts(&chunks), vec!["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn overlap_test() {
+        let chunks = Chunker::new()
+            .set_max_size(16)
+            .set_overlap(6)
+            .chunk_str("one two three four five six");
+        assert_eq!(
+            texts(&chunks),
+            vec!["one two three", "three four five", "five six"]
+        );
+    }
+
+    #[test]
+    fn bytes_test() {
+        let text = "héhé héhé";
+        let chunks = Chunker::new()
+            .set_unit(ChunkUnit::Bytes)
+            .set_max_size(5)
+            .chunk_str(text);
+        assert_eq!(texts(&chunks), vec!["héh", "é", "héh", "é"]);
+        assert!(chunks.iter().all(|chunk| chunk.text.len() <= 5));
+        assert_eq!((chunks[2].start, chunks[2].end), (5, 8));
+    }
+
+    #[test]
+    fn reader_test() {
+        let text = TEXT.repeat(1000);
+        let chunker = Chunker::new().set_max_size(100).set_overlap(20);
+
+        // Reads of one byte split the characters
+        struct OneByte<'a>(&'a [u8]);
+        impl Read for OneByte<'_> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = self.0.len().min(1).min(buf.len());
+                buf[..len].copy_from_slice(&self.0[..len]);
+                self.0 = &self.0[len..];
+                Ok(len)
+            }
+        }
+
+        let text = format!("é{}", text);
+        let chunks: Vec<_> = chunker
+            .chunk_reader(OneByte(text.as_bytes()))
+            .collect::<ExtractResult<_>>()
+            .unwrap();
+        assert_eq!(chunks, chunker.chunk_str(&text));
+    }
+
+    #[test]
+    fn pages_test() {
+        let pages = vec![
+            Page {
+                number: 1,
+                text: "one two".to_string(),
+                languages: Vec::new(),
+            },
+            Page {
+                number: 2,
+                text: "three".to_string(),
+                languages: Vec::new(),
+            },
+        ];
+        let chunks = Chunker::new().set_max_size(5).chunk_pages(pages);
+        let pages: Vec<_> = chunks
+            .iter()
+            .map(|chunk| (chunk.text.as_str(), chunk.page_number, chunk.start))
+            .collect();
+        assert_eq!(
+            pages,
+            vec![
+                ("one", Some(1), 0),
+                ("two", Some(1), 4),
+                ("three", Some(2), 0)
+            ]
+        );
+    }
+}
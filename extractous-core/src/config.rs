@@ -1,7 +1,11 @@
-use strum_macros::{Display, EnumString};
+use crate::{CharSet, Error, ExtractResult, OutputFormat};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize};
+use strum_macros::{Display, EnumString, VariantNames};
 
 /// OCR Strategy for PDF parsing
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString, VariantNames)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[allow(non_camel_case_types)]
 pub enum PdfOcrStrategy {
     NO_OCR,
@@ -15,7 +19,10 @@ pub enum PdfOcrStrategy {
 ///
 /// These settings are used to configure the behavior of the PDF parsing.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct PdfParserConfig {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::ocr_strategy"))]
     pub(crate) ocr_strategy: PdfOcrStrategy,
     pub(crate) extract_inline_images: bool,
     pub(crate) extract_unique_inline_images_only: bool,
@@ -96,6 +103,8 @@ impl PdfParserConfig {
 ///
 /// These settings are used to configure the behavior of the MSOffice parsing.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct OfficeParserConfig {
     pub(crate) extract_macros: bool,
     pub(crate) include_deleted_content: bool,
@@ -221,12 +230,18 @@ impl OfficeParserConfig {
 ///
 /// These settings are used to configure the behavior of the optical image recognition.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
 pub struct TesseractOcrConfig {
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::density"))]
     pub(crate) density: i32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::depth"))]
     pub(crate) depth: i32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::timeout_seconds"))]
     pub(crate) timeout_seconds: i32,
     pub(crate) enable_image_preprocessing: bool,
     pub(crate) apply_rotation: bool,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::language"))]
     pub(crate) language: String,
 }
 
@@ -294,3 +309,413 @@ impl TesseractOcrConfig {
         self
     }
 }
+
+/// Configuration of an [`crate::Extractor`] as plain data, to keep extraction profiles in
+/// configuration files. With the `serde` feature it can be loaded from any serde format, such as
+/// TOML, JSON or YAML. Missing keys take their default value and unknown keys are rejected.
+/// Invalid values fail the loading with an error that starts with the path of the key, for example
+/// `` ocr.density: `density` must be positive, got 0 ``. Build the extractor with
+/// [`crate::Extractor::from_config`].
+///
+/// The passwords and the cancellation token are not part of the configuration, set them on the
+/// extractor
+/// ```rust
+/// use extractous::{Extractor, ExtractorConfig, OutputFormat};
+/// let config = ExtractorConfig {
+///     output_format: OutputFormat::Markdown,
+///     ..ExtractorConfig::default()
+/// };
+/// let extractor = Extractor::from_config(config).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(default, deny_unknown_fields, remote = "Self")
+)]
+pub struct ExtractorConfig {
+    /// See [`crate::Extractor::set_extract_string_max_length`]. `None` for no limit, which is
+    /// written `0` in the configuration files, as formats such as TOML have no null.
+    /// Default: 500_000
+    #[cfg_attr(feature = "serde", serde(with = "de::max_length"))]
+    pub extract_string_max_length: Option<usize>,
+    /// See [`crate::Extractor::set_extract_string_strict`]. Default: false
+    pub extract_string_strict: bool,
+    /// The encoding of the streams by its name, for example `UTF-8` or `windows-1251`, see
+    /// [`CharSet`]. Default: UTF-8
+    pub encoding: CharSet,
+    /// The format of the extracted content, for example `PLAIN_TEXT` or `MARKDOWN`, see
+    /// [`OutputFormat`]. Default: PLAIN_TEXT
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::output_format"))]
+    pub output_format: OutputFormat,
+    /// See [`crate::Extractor::set_detect_languages`]. Default: false
+    pub detect_languages: bool,
+    /// The maximum duration of a single extraction in seconds, see
+    /// [`crate::Extractor::set_parse_timeout`]. Default: no timeout
+    #[cfg_attr(
+        feature = "serde",
+        serde(deserialize_with = "de::parse_timeout_seconds")
+    )]
+    pub parse_timeout_seconds: Option<f64>,
+    /// The mime types of the documents to parse, see
+    /// [`crate::Extractor::set_allowed_mime_types`]. Default: all types
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::allowed_mime_types"))]
+    pub allowed_mime_types: Vec<String>,
+    /// The mime types of the documents to never parse, see
+    /// [`crate::Extractor::set_denied_mime_types`]. Default: none
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "de::denied_mime_types"))]
+    pub denied_mime_types: Vec<String>,
+    /// The configuration of the PDF parser
+    pub pdf: PdfParserConfig,
+    /// The configuration of the Office parser
+    pub office: OfficeParserConfig,
+    /// The configuration of the Tesseract OCR
+    pub ocr: TesseractOcrConfig,
+}
+
+impl Default for ExtractorConfig {
+    fn default() -> Self {
+        Self {
+            extract_string_max_length: Some(500_000),
+            extract_string_strict: false,
+            encoding: CharSet::UTF_8,
+            output_format: OutputFormat::PlainText,
+            detect_languages: false,
+            parse_timeout_seconds: None,
+            allowed_mime_types: Vec::new(),
+            denied_mime_types: Vec::new(),
+            pdf: PdfParserConfig::default(),
+            office: OfficeParserConfig::default(),
+            ocr: TesseractOcrConfig::default(),
+        }
+    }
+}
+
+// `remote = "Self"` makes the derived implementations the inherent `ExtractorConfig::serialize`
+// and `ExtractorConfig::deserialize`. The trait implementations wrap them to track the path of
+// the values, which the errors of the nested configs do not know
+#[cfg(feature = "serde")]
+impl Serialize for ExtractorConfig {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ExtractorConfig::serialize(self, serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for ExtractorConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut track = serde_path_to_error::Track::new();
+        let deserializer = serde_path_to_error::Deserializer::new(deserializer, &mut track);
+        ExtractorConfig::deserialize(deserializer).map_err(|err| de::with_path(track.path(), err))
+    }
+}
+
+impl ExtractorConfig {
+    /// Checks the values of the configuration. Fails with [`Error::InvalidConfig`] naming the
+    /// offending key, for example `` `ocr.density` must be positive, got 0 ``
+    pub fn validate(&self) -> ExtractResult<()> {
+        check_positive("ocr.density", self.ocr.density).map_err(Error::InvalidConfig)?;
+        check_positive("ocr.depth", self.ocr.depth).map_err(Error::InvalidConfig)?;
+        check_positive("ocr.timeout_seconds", self.ocr.timeout_seconds)
+            .map_err(Error::InvalidConfig)?;
+        check_not_empty("ocr.language", &self.ocr.language).map_err(Error::InvalidConfig)?;
+        check_timeout("parse_timeout_seconds", self.parse_timeout_seconds)
+            .map_err(Error::InvalidConfig)?;
+        check_mime_types("allowed_mime_types", &self.allowed_mime_types)
+            .map_err(Error::InvalidConfig)?;
+        check_mime_types("denied_mime_types", &self.denied_mime_types)
+            .map_err(Error::InvalidConfig)?;
+        Ok(())
+    }
+}
+
+fn check_positive(key: &str, value: i32) -> Result<i32, String> {
+    if value > 0 {
+        Ok(value)
+    } else {
+        Err(format!("`{key}` must be positive, got {value}"))
+    }
+}
+
+fn check_not_empty<'a>(key: &str, value: &'a str) -> Result<&'a str, String> {
+    if value.trim().is_empty() {
+        Err(format!("`{key}` must not be empty"))
+    } else {
+        Ok(value)
+    }
+}
+
+fn check_timeout(key: &str, value: Option<f64>) -> Result<Option<f64>, String> {
+    match value {
+        // Also rejects NaN and the durations that do not fit a Duration
+        Some(seconds) if !(seconds > 0.0 && seconds < u64::MAX as f64) => Err(format!(
+            "`{key}` must be a positive number of seconds, got {seconds}"
+        )),
+        _ => Ok(value),
+    }
+}
+
+fn check_mime_types(key: &str, values: &[String]) -> Result<(), String> {
+    for value in values {
+        match value.trim().split_once('/') {
+            Some((top, sub)) if !top.is_empty() && !sub.is_empty() && !sub.contains('/') => {}
+            _ => {
+                return Err(format!(
+                    "`{key}` must only have mime types such as application/pdf or image/*, \
+                     got `{value}`"
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Deserializers of the fields with constrained values. The errors name the field, the
+/// [`ExtractorConfig`] adds its path, e.g. `ocr.density`
+#[cfg(feature = "serde")]
+mod de {
+    use super::*;
+    use serde::de::Error as _;
+    use std::str::FromStr;
+    use strum::VariantNames;
+
+    /// Prefixes the error with the path of the value, unless the error is about the whole config
+    pub(super) fn with_path<E: serde::de::Error>(path: serde_path_to_error::Path, err: E) -> E {
+        if path.iter().next().is_none() {
+            return err;
+        }
+        E::custom(format!("{path}: {err}"))
+    }
+
+    /// The maximum length of the strings, `0` for no limit. `null` is read as no limit too
+    pub(super) mod max_length {
+        use super::*;
+
+        pub(in crate::config) fn serialize<S: serde::Serializer>(
+            value: &Option<usize>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u64(value.unwrap_or(0) as u64)
+        }
+
+        pub(in crate::config) fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<usize>, D::Error> {
+            Ok(Option::<usize>::deserialize(deserializer)?.filter(|&max_length| max_length > 0))
+        }
+    }
+
+    fn positive<'de, D: Deserializer<'de>>(deserializer: D, key: &str) -> Result<i32, D::Error> {
+        check_positive(key, i32::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+
+    fn variant<'de, D, T>(deserializer: D, key: &str) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr + VariantNames,
+    {
+        let value = String::deserialize(deserializer)?;
+        T::from_str(&value).map_err(|_| {
+            D::Error::custom(format!(
+                "`{key}` must be one of {}, got `{value}`",
+                T::VARIANTS.join(", ")
+            ))
+        })
+    }
+
+    pub(super) fn density<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+        positive(deserializer, "density")
+    }
+
+    pub(super) fn depth<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i32, D::Error> {
+        positive(deserializer, "depth")
+    }
+
+    pub(super) fn timeout_seconds<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<i32, D::Error> {
+        positive(deserializer, "timeout_seconds")
+    }
+
+    pub(super) fn language<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        check_not_empty("language", &value).map_err(D::Error::custom)?;
+        Ok(value)
+    }
+
+    fn mime_types<'de, D: Deserializer<'de>>(
+        deserializer: D,
+        key: &str,
+    ) -> Result<Vec<String>, D::Error> {
+        let values = Vec::<String>::deserialize(deserializer)?;
+        check_mime_types(key, &values).map_err(D::Error::custom)?;
+        Ok(values)
+    }
+
+    pub(super) fn allowed_mime_types<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        mime_types(deserializer, "allowed_mime_types")
+    }
+
+    pub(super) fn denied_mime_types<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<String>, D::Error> {
+        mime_types(deserializer, "denied_mime_types")
+    }
+
+    pub(super) fn ocr_strategy<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<PdfOcrStrategy, D::Error> {
+        variant(deserializer, "ocr_strategy")
+    }
+
+    pub(super) fn output_format<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<OutputFormat, D::Error> {
+        variant(deserializer, "output_format")
+    }
+
+    pub(super) fn parse_timeout_seconds<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<f64>, D::Error> {
+        check_timeout("parse_timeout_seconds", Option::deserialize(deserializer)?)
+            .map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_test() {
+        assert!(ExtractorConfig::default().validate().is_ok());
+
+        let mut config = ExtractorConfig::default();
+        config.ocr = config.ocr.set_density(0);
+        let err = config.validate().unwrap_err();
+        assert!(matches!(err, Error::InvalidConfig(_)));
+        assert_eq!(err.to_string(), "`ocr.density` must be positive, got 0");
+
+        config = ExtractorConfig {
+            allowed_mime_types: vec!["application/pdf".to_string(), "image/*".to_string()],
+            ..ExtractorConfig::default()
+        };
+        assert!(config.validate().is_ok());
+        config.allowed_mime_types.push("image/".to_string());
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .ends_with("got `image/`"));
+
+        config = ExtractorConfig {
+            parse_timeout_seconds: Some(f64::NAN),
+            ..ExtractorConfig::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .starts_with("`parse_timeout_seconds`"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_test() {
+        let config: ExtractorConfig = serde_json::from_str(
+            r#"{
+                "extract_string_max_length": null,
+                "encoding": "windows-1252",
+                "output_format": "MARKDOWN",
+                "parse_timeout_seconds": 2.5,
+                "pdf": { "ocr_strategy": "OCR_ONLY" },
+                "ocr": { "density": 150, "language": "deu" }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(config.extract_string_max_length, None);
+        assert_eq!(config.encoding, CharSet::WINDOWS_1252);
+        assert_eq!(config.output_format, OutputFormat::Markdown);
+        assert_eq!(config.parse_timeout_seconds, Some(2.5));
+        assert_eq!(
+            config.pdf,
+            PdfParserConfig::new().set_ocr_strategy(PdfOcrStrategy::OCR_ONLY)
+        );
+        assert_eq!(config.office, OfficeParserConfig::default());
+        assert_eq!(
+            config.ocr,
+            TesseractOcrConfig::new()
+                .set_density(150)
+                .set_language("deu")
+        );
+
+        // Round trip
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(
+            serde_json::from_str::<ExtractorConfig>(&json).unwrap(),
+            config
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn toml_round_trip_test() {
+        let config = ExtractorConfig {
+            extract_string_max_length: None,
+            output_format: OutputFormat::Json,
+            parse_timeout_seconds: Some(30.0),
+            denied_mime_types: vec!["image/*".to_string()],
+            ocr: TesseractOcrConfig::new().set_language("deu"),
+            ..ExtractorConfig::default()
+        };
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("extract_string_max_length = 0"));
+        assert_eq!(toml::from_str::<ExtractorConfig>(&toml).unwrap(), config);
+
+        let config = ExtractorConfig::default();
+        let toml = toml::to_string(&config).unwrap();
+        assert!(toml.contains("extract_string_max_length = 500000"));
+        assert_eq!(toml::from_str::<ExtractorConfig>(&toml).unwrap(), config);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_invalid_test() {
+        let err = |json: &str| serde_json::from_str::<ExtractorConfig>(json).unwrap_err();
+
+        assert!(err(r#"{"ocr": {"density": -1}}"#)
+            .to_string()
+            .starts_with("ocr.density: `density` must be positive, got -1"));
+        assert!(err(r#"{"ocr": {"language": ""}}"#)
+            .to_string()
+            .starts_with("ocr.language: `language` must not be empty"));
+        assert!(err(r#"{"pdf": {"ocr_strategy": "SOMETIMES"}}"#)
+            .to_string()
+            .starts_with(
+                "pdf.ocr_strategy: `ocr_strategy` must be one of NO_OCR, OCR_ONLY, \
+                 OCR_AND_TEXT_EXTRACTION, AUTO, got `SOMETIMES`"
+            ));
+        assert!(err(r#"{"output_format": "pdf"}"#)
+            .to_string()
+            .starts_with("output_format: `output_format` must be one of PLAIN_TEXT"));
+        assert!(err(r#"{"denied_mime_types": ["image/*", "pdf"]}"#)
+            .to_string()
+            .starts_with("denied_mime_types: `denied_mime_types` must only have mime types"));
+        assert!(err(r#"{"ocr": {"dpi": 300}}"#)
+            .to_string()
+            .starts_with("ocr.dpi: unknown field `dpi`"));
+        assert!(err(r#"{"max_length": 300}"#)
+            .to_string()
+            .starts_with("max_length: unknown field `max_length`"));
+
+        // The nested configs alone report the field names
+        assert!(
+            serde_json::from_str::<TesseractOcrConfig>(r#"{"density": 0}"#)
+                .unwrap_err()
+                .to_string()
+                .starts_with("`density` must be positive, got 0")
+        );
+    }
+}
@@ -0,0 +1,337 @@
+use crate::language::languages_from_metadata;
+use crate::{DetectedLanguage, Metadata};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+
+// Tika keys for each field, in order of preference. The spelling depends on the parser that
+// produced the metadata: PDF, OOXML and legacy Office, ODF, EPUB or email.
+const TITLE_KEYS: &[&str] = &["dc:title", "pdf:docinfo:title", "title"];
+const AUTHOR_KEYS: &[&str] = &[
+    "dc:creator",
+    "meta:author",
+    "pdf:docinfo:creator",
+    "Author",
+    "creator",
+    "Message-From",
+];
+const CREATED_KEYS: &[&str] = &[
+    "dcterms:created",
+    "pdf:docinfo:created",
+    "meta:creation-date",
+    "xmp:CreateDate",
+    "Creation-Date",
+    "created",
+    "Message:Raw-Header:Date",
+];
+const MODIFIED_KEYS: &[&str] = &[
+    "dcterms:modified",
+    "pdf:docinfo:modified",
+    "meta:save-date",
+    "xmp:ModifyDate",
+    "Last-Modified",
+    "Last-Save-Date",
+    "modified",
+];
+const PAGE_COUNT_KEYS: &[&str] = &[
+    "xmpTPg:NPages",
+    "meta:page-count",
+    "meta:slide-count",
+    "Page-Count",
+    "Slide-Count",
+    "nbPage",
+];
+const WORD_COUNT_KEYS: &[&str] = &["meta:word-count", "Word-Count", "nbWord"];
+const LANGUAGE_KEYS: &[&str] = &["dc:language", "language", "Content-Language"];
+const CONTENT_TYPE_KEYS: &[&str] = &["Content-Type"];
+const PRODUCER_KEYS: &[&str] = &[
+    "pdf:producer",
+    "pdf:docinfo:producer",
+    "extended-properties:Application",
+    "Application-Name",
+    "generator",
+    "meta:generator",
+    "xmp:CreatorTool",
+];
+const ENCRYPTED_KEYS: &[&str] = &["pdf:encrypted", "encrypted"];
+
+// Tika key set when the content was cut at the extractor's `extract_string_max_length`
+const WRITE_LIMIT_REACHED_KEY: &str = "X-TIKA:EXCEPTION:write_limit_reached";
+
+/// Typed view of the most common document metadata
+///
+/// Tika names the same property differently depending on the format, for example the page count
+/// is `xmpTPg:NPages` for PDF and `meta:page-count` for ODF. This view maps the known variants to
+/// a single typed field. Fields are `None` (or empty) when the document does not have them or
+/// when their value can't be parsed. The raw Tika metadata stays available in `raw`.
+/// ```rust
+/// use extractous::{DocumentMetadata, Extractor};
+///
+/// let (_content, metadata) = Extractor::new()
+///     .extract_file_to_string("README.md")
+///     .unwrap();
+/// let document_metadata = DocumentMetadata::from(metadata);
+/// println!("{:?}", document_metadata.content_type);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct DocumentMetadata {
+    pub title: Option<String>,
+    pub authors: Vec<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+    pub page_count: Option<u32>,
+    pub word_count: Option<u64>,
+    pub language: Option<String>,
+    /// The languages detected in the content, most probable first. Only present when enabled
+    /// with [`crate::Extractor::set_detect_languages`]
+    pub detected_languages: Vec<DetectedLanguage>,
+    /// The mime type, which may include parameters such as `charset`
+    pub content_type: Option<String>,
+    /// The application that produced the document
+    pub producer: Option<String>,
+    pub encrypted: Option<bool>,
+    /// Whether the content was cut at the extractor's `extract_string_max_length`
+    pub truncated: bool,
+    /// The raw Tika metadata
+    pub raw: Metadata,
+}
+
+impl DocumentMetadata {
+    /// Builds the typed view of the given raw metadata
+    pub fn new(raw: Metadata) -> Self {
+        Self {
+            title: first_value(&raw, TITLE_KEYS).map(str::to_string),
+            authors: all_values(&raw, AUTHOR_KEYS),
+            created: first_parsed(&raw, CREATED_KEYS, parse_date),
+            modified: first_parsed(&raw, MODIFIED_KEYS, parse_date),
+            page_count: first_parsed(&raw, PAGE_COUNT_KEYS, |v| v.parse().ok()),
+            word_count: first_parsed(&raw, WORD_COUNT_KEYS, |v| v.parse().ok()),
+            language: first_value(&raw, LANGUAGE_KEYS).map(str::to_string),
+            detected_languages: languages_from_metadata(&raw),
+            content_type: first_value(&raw, CONTENT_TYPE_KEYS).map(str::to_string),
+            producer: first_value(&raw, PRODUCER_KEYS).map(str::to_string),
+            encrypted: first_parsed(&raw, ENCRYPTED_KEYS, |v| {
+                v.to_ascii_lowercase().parse().ok()
+            }),
+            truncated: is_truncated(&raw),
+            raw,
+        }
+    }
+}
+
+impl From<Metadata> for DocumentMetadata {
+    fn from(raw: Metadata) -> Self {
+        Self::new(raw)
+    }
+}
+
+impl From<&Metadata> for DocumentMetadata {
+    fn from(raw: &Metadata) -> Self {
+        Self::new(raw.clone())
+    }
+}
+
+/// Returns true if the content was cut at the extractor's `extract_string_max_length`
+pub(crate) fn is_truncated(raw: &Metadata) -> bool {
+    first_parsed(raw, &[WRITE_LIMIT_REACHED_KEY], |v| {
+        v.to_ascii_lowercase().parse().ok()
+    })
+    .unwrap_or(false)
+}
+
+/// Returns the non empty, trimmed values of the first key present in the metadata
+fn values<'a>(raw: &'a Metadata, keys: &[&str]) -> Vec<&'a str> {
+    keys.iter()
+        .filter_map(|key| raw.get(*key))
+        .map(|values| {
+            values
+                .iter()
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty() && *v != "null")
+                .collect::<Vec<_>>()
+        })
+        .find(|values| !values.is_empty())
+        .unwrap_or_default()
+}
+
+fn first_value<'a>(raw: &'a Metadata, keys: &[&str]) -> Option<&'a str> {
+    values(raw, keys).first().copied()
+}
+
+fn all_values(raw: &Metadata, keys: &[&str]) -> Vec<String> {
+    let mut all: Vec<String> = Vec::new();
+    for value in values(raw, keys) {
+        if !all.iter().any(|v| v == value) {
+            all.push(value.to_string());
+        }
+    }
+    all
+}
+
+/// Returns the first value of the given keys that can be parsed
+fn first_parsed<T>(raw: &Metadata, keys: &[&str], parse: impl Fn(&str) -> Option<T>) -> Option<T> {
+    keys.iter()
+        .filter_map(|key| raw.get(*key))
+        .flatten()
+        .find_map(|v| parse(v.trim()))
+}
+
+/// Parses the date formats found in Tika metadata. Dates without a timezone are taken as UTC
+fn parse_date(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(date) = DateTime::parse_from_rfc3339(value) {
+        return Some(date.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(date.and_utc());
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0).map(|date| date.and_utc());
+    }
+    // Email headers
+    DateTime::parse_from_rfc2822(value)
+        .ok()
+        .map(|date| date.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn metadata(entries: &[(&str, &[&str])]) -> Metadata {
+        entries
+            .iter()
+            .map(|(key, values)| {
+                (
+                    key.to_string(),
+                    values.iter().map(|v| v.to_string()).collect(),
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pdf_metadata_test() {
+        let raw = metadata(&[
+            ("dc:title", &["0000320193-22-000070"]),
+            ("dc:creator", &["EDGAR Online"]),
+            ("pdf:docinfo:creator", &["EDGAR Online"]),
+            ("dcterms:created", &["2022-07-29T10:03:21Z"]),
+            ("pdf:docinfo:modified", &["2022-07-29T10:03:28Z"]),
+            ("xmpTPg:NPages", &["28"]),
+            ("Content-Type", &["application/pdf"]),
+            ("pdf:producer", &["EDGRpdf Service"]),
+            ("pdf:encrypted", &["true"]),
+        ]);
+        let doc = DocumentMetadata::from(&raw);
+
+        assert_eq!(doc.title.as_deref(), Some("0000320193-22-000070"));
+        assert_eq!(doc.authors, vec!["EDGAR Online".to_string()]);
+        assert_eq!(
+            doc.created,
+            Some(Utc.with_ymd_and_hms(2022, 7, 29, 10, 3, 21).unwrap())
+        );
+        assert_eq!(
+            doc.modified,
+            Some(Utc.with_ymd_and_hms(2022, 7, 29, 10, 3, 28).unwrap())
+        );
+        assert_eq!(doc.page_count, Some(28));
+        assert_eq!(doc.content_type.as_deref(), Some("application/pdf"));
+        assert_eq!(doc.producer.as_deref(), Some("EDGRpdf Service"));
+        assert_eq!(doc.encrypted, Some(true));
+        assert_eq!(doc.raw, raw);
+    }
+
+    #[test]
+    fn office_and_odf_metadata_test() {
+        let doc = DocumentMetadata::from(metadata(&[
+            ("meta:author", &["Jane Doe", "John Doe"]),
+            ("meta:creation-date", &["2023-09-22T18:38:00"]),
+            ("meta:page-count", &["3"]),
+            ("meta:word-count", &["288"]),
+            ("dc:language", &["en-US"]),
+            ("generator", &["Pandoc/3.1.11.1"]),
+        ]));
+
+        assert_eq!(doc.title, None);
+        assert_eq!(doc.authors, vec!["Jane Doe", "John Doe"]);
+        assert_eq!(
+            doc.created,
+            Some(Utc.with_ymd_and_hms(2023, 9, 22, 18, 38, 0).unwrap())
+        );
+        assert_eq!(doc.page_count, Some(3));
+        assert_eq!(doc.word_count, Some(288));
+        assert_eq!(doc.language.as_deref(), Some("en-US"));
+        assert_eq!(doc.producer.as_deref(), Some("Pandoc/3.1.11.1"));
+        assert_eq!(doc.encrypted, None);
+    }
+
+    #[test]
+    fn detected_languages_test() {
+        let doc = DocumentMetadata::from(metadata(&[
+            ("X-TIKA:detected_language", &["fr", "it"]),
+            (
+                "X-TIKA:detected_language_confidence_raw",
+                &["0.857", "0.142"],
+            ),
+        ]));
+
+        assert_eq!(
+            doc.detected_languages,
+            vec![
+                DetectedLanguage {
+                    language: "fr".to_string(),
+                    confidence: 0.857,
+                },
+                DetectedLanguage {
+                    language: "it".to_string(),
+                    confidence: 0.142,
+                },
+            ]
+        );
+        assert_eq!(doc.language, None);
+    }
+
+    #[test]
+    fn truncation_test() {
+        let doc = DocumentMetadata::from(metadata(&[(WRITE_LIMIT_REACHED_KEY, &["true"])]));
+        assert!(doc.truncated);
+
+        let doc = DocumentMetadata::from(metadata(&[(WRITE_LIMIT_REACHED_KEY, &["false"])]));
+        assert!(!doc.truncated);
+        assert!(!DocumentMetadata::from(Metadata::new()).truncated);
+    }
+
+    #[test]
+    fn email_metadata_test() {
+        let doc = DocumentMetadata::from(metadata(&[
+            ("Message-From", &["Jane Doe <jane@example.com>"]),
+            (
+                "Message:Raw-Header:Date",
+                &["Wed, 5 Dec 2012 10:45:00 +0100"],
+            ),
+            ("dc:title", &["Meeting notes"]),
+        ]));
+
+        assert_eq!(doc.authors, vec!["Jane Doe <jane@example.com>"]);
+        assert_eq!(
+            doc.created,
+            Some(Utc.with_ymd_and_hms(2012, 12, 5, 9, 45, 0).unwrap())
+        );
+        assert_eq!(doc.title.as_deref(), Some("Meeting notes"));
+    }
+
+    #[test]
+    fn invalid_values_test() {
+        let doc = DocumentMetadata::from(metadata(&[
+            ("dc:title", &["  "]),
+            ("pdf:docinfo:title", &["Fallback title"]),
+            ("dcterms:created", &["not a date"]),
+            ("xmpTPg:NPages", &["many"]),
+            ("pdf:encrypted", &["null"]),
+        ]));
+
+        assert_eq!(doc.title.as_deref(), Some("Fallback title"));
+        assert_eq!(doc.created, None);
+        assert_eq!(doc.page_count, None);
+        assert_eq!(doc.encrypted, None);
+    }
+}
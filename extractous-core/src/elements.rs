@@ -0,0 +1,357 @@
+use crate::errors::ExtractResult;
+use crate::tables::{Table, TableBuilder};
+use crate::xhtml::{XhtmlEvent, XhtmlReader};
+use std::io::BufRead;
+
+/// Kind of a content [`Element`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ElementKind {
+    /// A heading, from `h1` to `h6`, e.g. from the heading styles of office documents
+    Heading,
+    Paragraph,
+    ListItem,
+    /// A table, its text has one line per row and the cells are separated by tabs
+    Table,
+    /// A table or figure caption
+    Caption,
+    /// The header of the pages of an office document
+    Header,
+    /// The footer of the pages of an office document
+    Footer,
+    /// The start of a new page or slide, without text
+    PageBreak,
+}
+
+/// A typed content element of a document, see [`crate::Extractor::extract_file_to_elements`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Element {
+    pub kind: ElementKind,
+    /// The text of the element, without surrounding whitespace
+    pub text: String,
+    /// The level of a heading, from 1 to 6. `None` for the other kinds
+    pub heading_level: Option<u8>,
+    /// The number of the page or slide holding the element, starting at 1. `None` for documents
+    /// without page markers, see [`crate::PageIter`]
+    pub page_number: Option<usize>,
+    /// The index of the heading of the section holding the element, in the list of elements.
+    /// For headings, it is the closest previous heading of a lower level
+    pub parent_section: Option<usize>,
+}
+
+/// The element being read
+struct Block {
+    kind: ElementKind,
+    heading_level: Option<u8>,
+    depth: usize,
+    text: String,
+    /// Builder of a table
+    table: Option<TableBuilder>,
+}
+
+/// Partitions the XHTML output of Tika into typed content elements
+pub(crate) fn parse_elements<R: BufRead>(xhtml: R) -> ExtractResult<Vec<Element>> {
+    Ok(partition(xhtml)?.elements)
+}
+
+/// Parses the tables of the XHTML output of Tika
+pub(crate) fn parse_tables<R: BufRead>(xhtml: R) -> ExtractResult<Vec<Table>> {
+    Ok(partition(xhtml)?.tables)
+}
+
+fn partition<R: BufRead>(xhtml: R) -> ExtractResult<Partitioner> {
+    let mut partitioner = Partitioner {
+        elements: Vec::new(),
+        tables: Vec::new(),
+        sections: Vec::new(),
+        depth: 0,
+        page_depth: None,
+        embedded_depth: None,
+        page_number: None,
+        page_title: None,
+        block: None,
+        loose_text: String::new(),
+    };
+
+    let mut reader = XhtmlReader::new(xhtml);
+    while let Some(event) = reader.next_event()? {
+        partitioner.handle(event);
+    }
+    partitioner.flush();
+
+    Ok(partitioner)
+}
+
+struct Partitioner {
+    elements: Vec<Element>,
+    tables: Vec<Table>,
+    /// Level and index of the enclosing headings, outermost first
+    sections: Vec<(u8, usize)>,
+    /// Element depth in the body
+    depth: usize,
+    /// Depth of the page element being read
+    page_depth: Option<usize>,
+    /// Depth of the outermost embedded document element being read
+    embedded_depth: Option<usize>,
+    page_number: Option<usize>,
+    /// First heading of the current page
+    page_title: Option<String>,
+    block: Option<Block>,
+    /// Text outside of any block element, returned as a paragraph
+    loose_text: String,
+}
+
+impl Partitioner {
+    fn handle(&mut self, event: XhtmlEvent) {
+        match event {
+            XhtmlEvent::Start { ref name, .. } => {
+                self.depth += 1;
+                if self.page_depth.is_none() && self.embedded_depth.is_none() {
+                    if event.is_embedded_start() {
+                        self.embedded_depth = Some(self.depth);
+                    } else if event.is_page_start() {
+                        self.page_depth = Some(self.depth);
+                        self.start_page();
+                        return;
+                    }
+                }
+
+                if let Some(block) = &mut self.block {
+                    if let Some(table) = &mut block.table {
+                        table.start(&event);
+                        return;
+                    }
+                    // Headers and footers hold their own paragraphs
+                    if matches!(block.kind, ElementKind::Header | ElementKind::Footer) {
+                        return;
+                    }
+                }
+
+                if let Some((kind, heading_level)) = block_kind(&event) {
+                    self.flush();
+                    self.block = Some(Block {
+                        kind,
+                        heading_level,
+                        depth: self.depth,
+                        text: String::new(),
+                        table: (kind == ElementKind::Table).then(TableBuilder::default),
+                    });
+                } else if name == "div" {
+                    self.flush_loose_text();
+                }
+            }
+            XhtmlEvent::End { ref name } => {
+                if let Some(block) = &mut self.block {
+                    if block.depth == self.depth {
+                        self.flush();
+                    } else if let Some(table) = &mut block.table {
+                        table.end(name);
+                    }
+                } else if name == "div" {
+                    self.flush_loose_text();
+                }
+
+                if self.page_depth == Some(self.depth) {
+                    self.page_depth = None;
+                }
+                if self.embedded_depth == Some(self.depth) {
+                    self.embedded_depth = None;
+                }
+                self.depth = self.depth.saturating_sub(1);
+            }
+            XhtmlEvent::Text(text) => match &mut self.block {
+                Some(Block {
+                    table: Some(table), ..
+                }) => table.text(&text),
+                Some(block) => block.text.push_str(&text),
+                None => self.loose_text.push_str(&text),
+            },
+        }
+    }
+
+    fn start_page(&mut self) {
+        self.flush();
+        let page_number = self.page_number.map_or(1, |number| number + 1);
+        self.page_number = Some(page_number);
+        self.page_title = None;
+        if page_number > 1 {
+            self.push(ElementKind::PageBreak, String::new(), None);
+        }
+    }
+
+    /// Pushes the text read so far and the element being read
+    fn flush(&mut self) {
+        self.flush_loose_text();
+        let Some(block) = self.block.take() else {
+            return;
+        };
+        if let Some(table) = block.table {
+            self.push_table(table);
+            return;
+        }
+
+        let text = block.text.trim().to_string();
+        if !text.is_empty() {
+            if block.kind == ElementKind::Heading && self.page_title.is_none() {
+                self.page_title = self.page_number.map(|_| text.clone());
+            }
+            self.push(block.kind, text, block.heading_level);
+        }
+    }
+
+    fn push_table(&mut self, table: TableBuilder) {
+        let (rows, header_rows) = table.finish();
+        if rows.is_empty() {
+            return;
+        }
+
+        let text = rows
+            .iter()
+            .map(|row| {
+                let cells: Vec<_> = row.iter().map(|cell| cell.text.as_str()).collect();
+                cells.join("\t")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        self.tables.push(Table {
+            rows,
+            header_rows,
+            page_number: self.page_number,
+            page_title: self.page_title.clone(),
+            element_index: self.elements.len(),
+        });
+        self.push(ElementKind::Table, text, None);
+    }
+
+    fn flush_loose_text(&mut self) {
+        let text = std::mem::take(&mut self.loose_text);
+        if !text.trim().is_empty() {
+            self.push(ElementKind::Paragraph, text.trim().to_string(), None);
+        }
+    }
+
+    fn push(&mut self, kind: ElementKind, text: String, heading_level: Option<u8>) {
+        let index = self.elements.len();
+        if let Some(level) = heading_level {
+            while self.sections.last().is_some_and(|(l, _)| *l >= level) {
+                self.sections.pop();
+            }
+        }
+        let parent_section = self.sections.last().map(|(_, index)| *index);
+        if let Some(level) = heading_level {
+            self.sections.push((level, index));
+        }
+
+        self.elements.push(Element {
+            kind,
+            text,
+            heading_level,
+            page_number: self.page_number,
+            parent_section,
+        });
+    }
+}
+
+/// Returns the kind of element the event starts, and the level of headings
+fn block_kind(event: &XhtmlEvent) -> Option<(ElementKind, Option<u8>)> {
+    let XhtmlEvent::Start { name, .. } = event else {
+        return None;
+    };
+    let kind = match name.as_str() {
+        "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+            let level = name[1..].parse().ok();
+            return Some((ElementKind::Heading, level));
+        }
+        "p" if event.has_class("caption") => ElementKind::Caption,
+        "p" | "pre" | "blockquote" => ElementKind::Paragraph,
+        "li" => ElementKind::ListItem,
+        "table" => ElementKind::Table,
+        "caption" | "figcaption" => ElementKind::Caption,
+        "div" if event.has_class("header") => ElementKind::Header,
+        "div" if event.has_class("footer") => ElementKind::Footer,
+        _ => return None,
+    };
+    Some((kind, None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn element(
+        kind: ElementKind,
+        text: &str,
+        page_number: Option<usize>,
+        parent_section: Option<usize>,
+    ) -> Element {
+        Element {
+            kind,
+            text: text.to_string(),
+            heading_level: None,
+            page_number,
+            parent_section,
+        }
+    }
+
+    fn heading(level: u8, text: &str, parent_section: Option<usize>) -> Element {
+        Element {
+            heading_level: Some(level),
+            ..element(ElementKind::Heading, text, None, parent_section)
+        }
+    }
+
+    #[test]
+    fn sections_test() {
+        let xhtml = r#"<html><head><title>Doc</title></head><body>
+<div class="header"><p>Company</p>
+<p>Confidential</p>
+</div>
+<h1>Report</h1>
+<p>Intro</p>
+<h2>Details</h2>
+<ul><li>First</li>
+<li>Second</li></ul>
+<p class="Caption">Table 1: Results</p>
+<table><tbody><tr>	<td>a</td>	<td></td></tr>
+<tr>	<td>c</td>	<td>d</td></tr></tbody></table>
+<h1>Annex</h1>
+<div class="footer"><p>Page footer</p></div>
+</body></html>"#;
+
+        use ElementKind::*;
+        assert_eq!(
+            parse_elements(xhtml.as_bytes()).unwrap(),
+            vec![
+                element(Header, "Company\nConfidential", None, None),
+                heading(1, "Report", None),
+                element(Paragraph, "Intro", None, Some(1)),
+                heading(2, "Details", Some(1)),
+                element(ListItem, "First", None, Some(3)),
+                element(ListItem, "Second", None, Some(3)),
+                element(Caption, "Table 1: Results", None, Some(3)),
+                element(Table, "a\t\nc\td", None, Some(3)),
+                heading(1, "Annex", None),
+                element(Footer, "Page footer", None, Some(8)),
+            ]
+        );
+    }
+
+    #[test]
+    fn pages_test() {
+        let xhtml = r#"<html><body>
+<div class="page"><p>One</p></div>
+<div class="page"><p>Two</p>
+Loose text</div>
+</body></html>"#;
+
+        use ElementKind::*;
+        assert_eq!(
+            parse_elements(xhtml.as_bytes()).unwrap(),
+            vec![
+                element(Paragraph, "One", Some(1), None),
+                element(PageBreak, "", Some(2), None),
+                element(Paragraph, "Two", Some(2), None),
+                element(Paragraph, "Loose text", Some(2), None),
+            ]
+        );
+    }
+}
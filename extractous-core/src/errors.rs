@@ -1,6 +1,28 @@
+use std::fmt;
 use std::io;
 use std::str::Utf8Error;
 
+/// Details of an error reported by Tika, see [`Error::context`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    /// The error message
+    pub message: String,
+    /// The path of the file or the url being extracted, `None` for bytes and streams
+    pub path: Option<String>,
+    /// The mime type of the document, `None` if it was not detected before the error
+    pub mime_type: Option<String>,
+    /// The class name of the java exception that caused the error
+    pub java_class: Option<String>,
+    /// The stack trace of the java exception
+    pub stack_trace: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 /// Represent errors returned by extractous
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
@@ -21,30 +43,201 @@ pub enum Error {
 
     #[error("{0}")]
     JniEnvCall(&'static str),
+
+    /// The extraction took longer than the extractor's `parse_timeout`
+    #[error("{0}")]
+    Timeout(ErrorContext),
+
+    /// The extraction was aborted through its [`crate::CancellationToken`]
+    #[error("{0}")]
+    Cancelled(ErrorContext),
+
+    /// The extractor's `encoding` is not a charset of the native image
+    #[error("{0}")]
+    UnsupportedEncoding(String),
+
+    /// The file to extract does not exist
+    #[error("{0}")]
+    FileNotFound(ErrorContext),
+
+    /// The file to extract cannot be read with the permissions of the process
+    #[error("{0}")]
+    PermissionDenied(ErrorContext),
+
+    /// Tika has no parser for the format of the document
+    #[error("{0}")]
+    UnsupportedFormat(ErrorContext),
+
+    /// The document is encrypted and cannot be decrypted
+    #[error("{0}")]
+    EncryptedDocument(ErrorContext),
+
+    /// The document is damaged or not of the format it claims to be
+    #[error("{0}")]
+    CorruptDocument(ErrorContext),
+
+    /// The document is empty
+    #[error("{0}")]
+    ZeroByteFile(ErrorContext),
+
+    /// The parser stopped after writing the maximum number of characters
+    #[error("{0}")]
+    WriteLimitReached(ErrorContext),
+
+    /// Tesseract failed, most likely because it or the OCR language pack is not installed
+    #[error("{0}")]
+    OcrFailure(ErrorContext),
+
+    /// The url to extract is invalid or cannot be fetched
+    #[error("{0}")]
+    NetworkError(ErrorContext),
+
+    /// The mime type of the document is denied, or not allowed, by the extractor. The context
+    /// has the detected mime type. The document is rejected before any of its content is parsed
+    #[error("{0}")]
+    Rejected(ErrorContext),
+
+    /// A value of an [`crate::ExtractorConfig`] is invalid, the message names its key, or the
+    /// tika-config.xml given to the extractor cannot be loaded
+    #[error("{0}")]
+    InvalidConfig(String),
+}
+
+impl Error {
+    /// Returns the details of the error if Tika reported them: the path, the mime type, and the
+    /// class and stack trace of the java exception
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Error::FileNotFound(context)
+            | Error::PermissionDenied(context)
+            | Error::UnsupportedFormat(context)
+            | Error::EncryptedDocument(context)
+            | Error::CorruptDocument(context)
+            | Error::ZeroByteFile(context)
+            | Error::WriteLimitReached(context)
+            | Error::OcrFailure(context)
+            | Error::NetworkError(context)
+            | Error::Rejected(context)
+            | Error::Timeout(context)
+            | Error::Cancelled(context) => Some(context),
+            _ => None,
+        }
+    }
 }
 
 // Implement the conversion from our Error type to io::Error
 // This allows us to use the ? when implementing std::io traits such as: Read, Write Seek etc ...
 impl From<Error> for io::Error {
     fn from(err: Error) -> Self {
-        match err {
-            Error::IoError(msg) => {
-                io::Error::new(io::ErrorKind::Other, format!("Io error: {}", msg))
-            }
-            Error::ParseError(msg) => {
-                io::Error::new(io::ErrorKind::Other, format!("Parse error: {}", msg))
-            }
-            Error::Utf8Error(e) => {
-                io::Error::new(io::ErrorKind::Other, format!("UTF8 error: {}", e))
+        // Keep the error as the source of the io::Error, so it can be recovered with
+        // `io::Error::get_ref` and `downcast_ref::<Error>`
+        let kind = match err {
+            Error::ParseError(_)
+            | Error::Utf8Error(_)
+            | Error::CorruptDocument(_)
+            | Error::ZeroByteFile(_) => io::ErrorKind::InvalidData,
+            Error::Timeout(_) => io::ErrorKind::TimedOut,
+            Error::UnsupportedEncoding(_) | Error::InvalidConfig(_) => io::ErrorKind::InvalidInput,
+            Error::FileNotFound(_) => io::ErrorKind::NotFound,
+            Error::PermissionDenied(_) | Error::EncryptedDocument(_) => {
+                io::ErrorKind::PermissionDenied
             }
-            Error::JniError(e) => io::Error::new(io::ErrorKind::Other, format!("JNI error: {}", e)),
-            Error::JniEnvCall(msg) => {
-                io::Error::new(io::ErrorKind::Other, format!("JNI env call error: {}", msg))
-            }
-            _ => io::Error::new(io::ErrorKind::Other, "Unknown error"),
+            Error::UnsupportedFormat(_) | Error::Rejected(_) => io::ErrorKind::Unsupported,
+            _ => io::ErrorKind::Other,
+        };
+        io::Error::new(kind, err)
+    }
+}
+
+// Implement the conversion from io::Error, which recovers the error kept as the source of the
+// io::Error by the conversion above
+impl From<&io::Error> for Error {
+    fn from(err: &io::Error) -> Self {
+        match err.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+            Some(Error::Unknown(msg)) => Error::Unknown(msg.clone()),
+            Some(Error::ParseError(msg)) => Error::ParseError(msg.clone()),
+            Some(Error::Utf8Error(e)) => Error::Utf8Error(*e),
+            Some(Error::JniEnvCall(msg)) => Error::JniEnvCall(msg),
+            Some(Error::UnsupportedEncoding(msg)) => Error::UnsupportedEncoding(msg.clone()),
+            Some(Error::IoError(msg)) => Error::IoError(msg.clone()),
+            Some(Error::InvalidConfig(msg)) => Error::InvalidConfig(msg.clone()),
+            Some(Error::FileNotFound(c)) => Error::FileNotFound(c.clone()),
+            Some(Error::PermissionDenied(c)) => Error::PermissionDenied(c.clone()),
+            Some(Error::UnsupportedFormat(c)) => Error::UnsupportedFormat(c.clone()),
+            Some(Error::EncryptedDocument(c)) => Error::EncryptedDocument(c.clone()),
+            Some(Error::CorruptDocument(c)) => Error::CorruptDocument(c.clone()),
+            Some(Error::ZeroByteFile(c)) => Error::ZeroByteFile(c.clone()),
+            Some(Error::WriteLimitReached(c)) => Error::WriteLimitReached(c.clone()),
+            Some(Error::OcrFailure(c)) => Error::OcrFailure(c.clone()),
+            Some(Error::NetworkError(c)) => Error::NetworkError(c.clone()),
+            Some(Error::Rejected(c)) => Error::Rejected(c.clone()),
+            Some(Error::Timeout(c)) => Error::Timeout(c.clone()),
+            Some(Error::Cancelled(c)) => Error::Cancelled(c.clone()),
+            // Not clonable
+            Some(Error::JniError(e)) => Error::IoError(e.to_string()),
+            None => Error::IoError(err.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        let msg = err.to_string();
+        match err.into_inner().map(|e| e.downcast::<Error>()) {
+            Some(Ok(e)) => *e,
+            _ => Error::IoError(msg),
         }
     }
 }
 
 /// Result that is a wrapper of Result<T, extractous::Error>
 pub type ExtractResult<T> = Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn io_error_round_trip_test() {
+        let err = io::Error::from(Error::ParseError(
+            "org.xml.sax.SAXException: bad".to_string(),
+        ));
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(err.to_string(), "org.xml.sax.SAXException: bad");
+        assert!(matches!(
+            err.get_ref().and_then(|e| e.downcast_ref::<Error>()),
+            Some(Error::ParseError(_))
+        ));
+        assert!(matches!(Error::from(&err), Error::ParseError(msg) if msg.contains("SAX")));
+        assert!(matches!(Error::from(err), Error::ParseError(msg) if msg.contains("SAX")));
+
+        let err = io::Error::from(Error::Timeout(ErrorContext {
+            message: "Parsing timed out after 300 ms".to_string(),
+            ..Default::default()
+        }));
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(matches!(Error::from(err), Error::Timeout(c) if c.message.contains("300 ms")));
+
+        let err = io::Error::new(io::ErrorKind::NotFound, "missing");
+        assert!(matches!(Error::from(err), Error::IoError(msg) if msg == "missing"));
+    }
+
+    #[test]
+    fn error_context_test() {
+        let context = ErrorContext {
+            message: "java.nio.file.NoSuchFileException: missing.pdf".to_string(),
+            path: Some("missing.pdf".to_string()),
+            java_class: Some("java.nio.file.NoSuchFileException".to_string()),
+            ..Default::default()
+        };
+        let err = Error::FileNotFound(context.clone());
+        assert_eq!(err.to_string(), context.message);
+        assert_eq!(err.context(), Some(&context));
+        assert_eq!(Error::IoError("io".to_string()).context(), None);
+
+        let err = io::Error::from(err);
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(matches!(Error::from(&err), Error::FileNotFound(c) if c == context));
+        assert!(matches!(Error::from(err), Error::FileNotFound(c) if c == context));
+    }
+}
@@ -1,21 +1,268 @@
-use crate::errors::ExtractResult;
+use crate::document_metadata::is_truncated;
+use crate::elements::{parse_elements, parse_tables};
+use crate::errors::{Error, ErrorContext, ExtractResult};
+use crate::markdown::{xhtml_to_markdown, MarkdownReader};
 use crate::tika;
 use crate::tika::JReaderInputStream;
-use crate::{OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
+use crate::{BatchIter, BatchOrder, BatchSource};
+use crate::{CancellationToken, ParseControl};
+use crate::{DetectedLanguage, Element, Page, PageIter, Table};
+use crate::{ExtractorConfig, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig};
 use std::collections::HashMap;
-use strum_macros::{Display, EnumString};
+use std::fmt;
+use std::fs;
+use std::io::{BufReader, Cursor, Read};
+use std::ops::Deref;
+use std::path::Path;
+use std::time::Duration;
+use strum_macros::{Display, EnumString, VariantNames};
 
 /// Metadata type alias
 pub type Metadata = HashMap<String, Vec<String>>;
 
-/// CharSet enum of all supported encodings
-#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+/// CharSet enum of the encodings of the extracted streams. The common encodings have their own
+/// variant and any other charset of the native image can be given by name with [`CharSet::Custom`].
+/// Displays as the Java name of the charset, for example `UTF-8` or `windows-1252`
+///
+/// Unlike in previous versions, `CharSet` is not `Copy` because [`CharSet::Custom`] owns its
+/// name. Clone it instead.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Display, EnumString)]
 #[allow(non_camel_case_types)]
 pub enum CharSet {
     #[default]
+    #[strum(to_string = "UTF-8", serialize = "UTF_8")]
     UTF_8,
+    #[strum(to_string = "US-ASCII", serialize = "US_ASCII")]
     US_ASCII,
+    #[strum(to_string = "UTF-16BE", serialize = "UTF_16BE")]
     UTF_16BE,
+    #[strum(to_string = "UTF-16LE", serialize = "UTF_16LE")]
+    UTF_16LE,
+    /// UTF-16 with a byte order mark
+    #[strum(to_string = "UTF-16", serialize = "UTF_16")]
+    UTF_16,
+    /// Latin-1
+    #[strum(to_string = "ISO-8859-1", serialize = "ISO_8859_1")]
+    ISO_8859_1,
+    /// Latin-9, Latin-1 with the euro sign
+    #[strum(to_string = "ISO-8859-15", serialize = "ISO_8859_15")]
+    ISO_8859_15,
+    #[strum(to_string = "windows-1252", serialize = "WINDOWS_1252")]
+    WINDOWS_1252,
+    #[strum(to_string = "Shift_JIS", serialize = "SHIFT_JIS")]
+    SHIFT_JIS,
+    #[strum(to_string = "EUC-JP", serialize = "EUC_JP")]
+    EUC_JP,
+    #[strum(to_string = "EUC-KR", serialize = "EUC_KR")]
+    EUC_KR,
+    #[strum(to_string = "GBK")]
+    GBK,
+    #[strum(to_string = "Big5", serialize = "BIG5")]
+    BIG5,
+    #[strum(to_string = "KOI8-R", serialize = "KOI8_R")]
+    KOI8_R,
+    /// Any other charset by its Java name or one of its aliases, for example `windows-1251`.
+    /// The name is checked against the charsets of the native image when extracting, unsupported
+    /// names fail with [`crate::Error::UnsupportedEncoding`]. Parsing a string that is not one of
+    /// the names above gives this variant
+    #[strum(default)]
+    Custom(String),
+}
+
+// Charsets are (de)serialized by their name, as for Display and FromStr
+#[cfg(feature = "serde")]
+impl serde::Serialize for CharSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CharSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        // Unknown names give the Custom variant, so parsing never fails
+        Ok(name.parse().unwrap_or(CharSet::Custom(name)))
+    }
+}
+
+/// Format of the extracted content, for both the streams and the `*_to_string` functions
+#[derive(Debug, Clone, Default, Copy, PartialEq, Eq, Hash, Display, EnumString, VariantNames)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum OutputFormat {
+    /// The text of the document body
+    #[default]
+    PlainText,
+    /// The XHTML document built by Tika, with the metadata as `meta` elements of its head
+    Xhtml,
+    /// The same document as HTML
+    Html,
+    /// Only the elements inside the body of the XHTML document, without the head and its `meta`
+    /// elements. There is no root element when the body has several children
+    BodyXhtml,
+    /// A JSON object with the text of the body as `content` and the metadata as `metadata`, which
+    /// maps each key to its list of values. For streams, the metadata is the one known once the
+    /// parsing is done, which is more complete than the metadata returned with the stream
+    Json,
+    /// GitHub flavored Markdown converted from the XHTML. Headings, lists, bold and italic text,
+    /// links and tables are rendered and pages or slides are separated by `---`.
+    /// Markdown streams are UTF-8 only, streams with another `encoding` fail with
+    /// [`crate::Error::UnsupportedEncoding`]
+    Markdown,
+}
+
+/// Describes which step of the Tika detection decided the mime type of a document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Display, EnumString)]
+#[strum(serialize_all = "SCREAMING_SNAKE_CASE")]
+pub enum DetectionSource {
+    /// The magic bytes at the start of the content
+    Magic,
+    /// The file name or the extension of the resource, or its declared content type, see
+    /// [`ExtractOptions`]
+    Filename,
+    /// Inspection of a container format, for example the entries of a zip or an OLE2 file
+    Container,
+    /// None of the detectors recognized the content, the mime type is `application/octet-stream`
+    Unknown,
+}
+
+/// Result of a mime type detection. Detection only inspects the start of the content and, for
+/// container formats, their directory entries. It never performs a full parse.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectResult {
+    /// The detected mime type, for example `application/pdf`
+    pub mime_type: String,
+    /// The detection step that decided the mime type
+    pub source: DetectionSource,
+    /// The metadata collected by Tika while detecting, e.g. resource name and content length
+    pub metadata: Metadata,
+}
+
+/// Hints about a document extracted from bytes, a reader or an url, given to Tika before the
+/// parsing. Without them the mime type of such documents is detected from their content only.
+/// ```rust
+/// use extractous::{ExtractOptions, Extractor};
+///
+/// let options = ExtractOptions {
+///     file_name: Some("data.csv".to_string()),
+///     ..Default::default()
+/// };
+/// let (content, metadata) = Extractor::new()
+///     .extract_bytes_to_string_with(b"name,count\nfoo,1\n", &options)
+///     .unwrap();
+/// println!("{:?}: {}", metadata.get("Content-Type"), content);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtractOptions {
+    /// The file name of the document, for example `report.csv`. Its extension is used by the
+    /// detection and it is reported as the `resourceName` metadata
+    pub file_name: Option<String>,
+    /// The declared mime type of the document, for example `text/csv`. The detection only
+    /// trusts it when it does not contradict the content
+    pub content_type: Option<String>,
+    /// The declared charset of a text document, for example `windows-1252`
+    pub charset: Option<String>,
+}
+
+/// The text extracted by the `*_to_string` functions. Derefs to `str`, so it can be used like
+/// the extracted string
+/// ```rust
+/// use extractous::Extractor;
+///
+/// let extractor = Extractor::new().set_extract_string_max_length(100);
+/// let (text, _metadata) = extractor.extract_file_to_string("README.md").unwrap();
+/// if text.truncated {
+///     println!("first 100 characters: {}", text.trim());
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtractedText {
+    /// The extracted text, of at most the extractor's `extract_string_max_length` characters
+    pub content: String,
+    /// Whether the text was cut at the extractor's `extract_string_max_length`. Never true in
+    /// strict mode, which fails with [`Error::WriteLimitReached`] instead
+    pub truncated: bool,
+}
+
+impl ExtractedText {
+    /// Returns the extracted string
+    pub fn into_string(self) -> String {
+        self.content
+    }
+}
+
+impl Deref for ExtractedText {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.content
+    }
+}
+
+impl AsRef<str> for ExtractedText {
+    fn as_ref(&self) -> &str {
+        &self.content
+    }
+}
+
+impl fmt::Display for ExtractedText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.content)
+    }
+}
+
+impl From<ExtractedText> for String {
+    fn from(text: ExtractedText) -> Self {
+        text.content
+    }
+}
+
+impl PartialEq<str> for ExtractedText {
+    fn eq(&self, other: &str) -> bool {
+        self.content == other
+    }
+}
+
+impl PartialEq<&str> for ExtractedText {
+    fn eq(&self, other: &&str) -> bool {
+        self.content == *other
+    }
+}
+
+impl PartialEq<String> for ExtractedText {
+    fn eq(&self, other: &String) -> bool {
+        &self.content == other
+    }
+}
+
+impl PartialEq<ExtractedText> for String {
+    fn eq(&self, other: &ExtractedText) -> bool {
+        self == &other.content
+    }
+}
+
+impl PartialEq<ExtractedText> for &str {
+    fn eq(&self, other: &ExtractedText) -> bool {
+        *self == other.content
+    }
+}
+
+/// A document extracted by the recursive extraction. The container document and each of its
+/// embedded documents (attachments, archive entries, OLE objects ...) is returned separately.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedDocument {
+    /// The extracted text of this document only, without the content of its embedded documents
+    pub content: String,
+    /// The metadata of this document
+    pub metadata: Metadata,
+    /// The path of this document inside the container, for example `/attachment.zip/report.pdf`.
+    /// `None` for the container document itself
+    pub embedded_path: Option<String>,
+    /// The nesting depth of this document. 0 for the container document, 1 for its direct
+    /// embedded documents and so on
+    pub depth: usize,
 }
 
 /// StreamReader implements std::io::Read
@@ -35,13 +282,75 @@ pub enum CharSet {
 /// println!("{}", content);
 /// ```
 ///
+/// Failed reads return an [`std::io::Error`] whose source is the [`crate::Error`] that caused
+/// them, available with `get_ref()` and `downcast_ref::<extractous::Error>()`. The parsing runs in
+/// the background while the stream is read, so an exception thrown by the parser, for example
+/// [`crate::Error::CorruptDocument`] for a corrupt file, surfaces from the reads with the
+/// [`crate::ErrorContext`] of the exception. Timeouts and cancellations are
+/// [`crate::Error::Timeout`] and [`crate::Error::Cancelled`].
+///
+/// The metadata returned with the stream is the one known when the parsing starts. Parsers add
+/// keys such as the page count or the embedded resources while parsing, use
+/// [`StreamReader::final_metadata`] or [`StreamReader::finish`] to get the complete metadata.
 pub struct StreamReader {
-    pub(crate) inner: JReaderInputStream,
+    inner: StreamSource,
+}
+
+enum StreamSource {
+    Tika(JReaderInputStream),
+    /// Markdown converted from the XHTML stream of Tika
+    Markdown(Box<MarkdownReader<BufReader<StreamReader>>>),
+}
+
+impl StreamReader {
+    pub(crate) fn new(inner: JReaderInputStream) -> Self {
+        Self {
+            inner: StreamSource::Tika(inner),
+        }
+    }
+
+    /// Converts this stream of UTF-8 XHTML to a stream of Markdown
+    fn into_markdown(self) -> Self {
+        Self {
+            inner: StreamSource::Markdown(Box::new(MarkdownReader::new(BufReader::new(self)))),
+        }
+    }
+
+    /// Returns the metadata once the parsing is done, the same as the metadata of the
+    /// `*_to_string` functions. `None` until the end of the stream is reached
+    pub fn final_metadata(&self) -> Option<&Metadata> {
+        match &self.inner {
+            StreamSource::Tika(inner) => inner.final_metadata(),
+            StreamSource::Markdown(inner) => inner.get_ref().get_ref().final_metadata(),
+        }
+    }
+
+    /// Reads the rest of the stream, discarding it, and returns the metadata once the parsing is
+    /// done. Blocks until the parsing ends
+    pub fn finish(mut self) -> ExtractResult<Metadata> {
+        std::io::copy(&mut self, &mut std::io::sink())?;
+        self.final_metadata()
+            .cloned()
+            .ok_or_else(|| Error::Unknown("No metadata at the end of the stream".to_string()))
+    }
 }
 
 impl std::io::Read for StreamReader {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
-        self.inner.read(buf)
+        match &mut self.inner {
+            StreamSource::Tika(inner) => inner.read(buf),
+            StreamSource::Markdown(inner) => inner.read(buf),
+        }
+    }
+}
+
+/// Candidate passwords of the encrypted documents, kept out of the `Debug` output
+#[derive(Clone, Default)]
+struct Passwords(Vec<String>);
+
+impl fmt::Debug for Passwords {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{} redacted]", self.0.len())
     }
 }
 
@@ -60,23 +369,37 @@ impl std::io::Read for StreamReader {
 ///
 #[derive(Debug, Clone)]
 pub struct Extractor {
-    extract_string_max_length: i32,
+    extract_string_max_length: Option<usize>,
+    extract_string_strict: bool,
     encoding: CharSet,
     pdf_config: PdfParserConfig,
     office_config: OfficeParserConfig,
     ocr_config: TesseractOcrConfig,
-    xml_output: bool,
+    passwords: Passwords,
+    tika_config: Option<tika::JTikaConfig>,
+    allowed_mime_types: Vec<String>,
+    denied_mime_types: Vec<String>,
+    output_format: OutputFormat,
+    detect_languages: bool,
+    control: ParseControl,
 }
 
 impl Default for Extractor {
     fn default() -> Self {
         Self {
-            extract_string_max_length: 500_000, // 500KB
+            extract_string_max_length: Some(500_000), // 500KB
+            extract_string_strict: false,
             encoding: CharSet::UTF_8,
             pdf_config: PdfParserConfig::default(),
             office_config: OfficeParserConfig::default(),
             ocr_config: TesseractOcrConfig::default(),
-            xml_output: false,
+            passwords: Passwords::default(),
+            tika_config: None,
+            allowed_mime_types: Vec::new(),
+            denied_mime_types: Vec::new(),
+            output_format: OutputFormat::PlainText,
+            detect_languages: false,
+            control: ParseControl::default(),
         }
     }
 }
@@ -86,15 +409,49 @@ impl Extractor {
         Self::default()
     }
 
-    /// Set the maximum length of the extracted text. Used only for extract_to_string functions
+    /// Creates an extractor from a configuration, for example loaded from a file with the
+    /// `serde` feature, see [`ExtractorConfig`]. Fails with [`Error::InvalidConfig`] naming the
+    /// offending key if a value is invalid
+    pub fn from_config(config: ExtractorConfig) -> ExtractResult<Self> {
+        config.validate()?;
+        let mut extractor = Self::new()
+            .set_extract_string_max_length(config.extract_string_max_length)
+            .set_extract_string_strict(config.extract_string_strict)
+            .set_encoding(config.encoding)
+            .set_output_format(config.output_format)
+            .set_detect_languages(config.detect_languages)
+            .set_allowed_mime_types(&config.allowed_mime_types)
+            .set_denied_mime_types(&config.denied_mime_types)
+            .set_pdf_config(config.pdf)
+            .set_office_config(config.office)
+            .set_ocr_config(config.ocr);
+        if let Some(seconds) = config.parse_timeout_seconds {
+            extractor = extractor.set_parse_timeout(Duration::from_secs_f64(seconds));
+        }
+        Ok(extractor)
+    }
+
+    /// Set the maximum length of the extracted text, in characters, or `None` for no limit. Used
+    /// only for extract_to_string and recursive functions. Longer content is cut at the limit and
+    /// flagged as truncated, see [`ExtractedText::truncated`]. Limits above `i32::MAX`
+    /// are capped to it, the maximum length of a java string
     /// Default: 500_000
-    pub fn set_extract_string_max_length(mut self, max_length: i32) -> Self {
-        self.extract_string_max_length = max_length;
+    pub fn set_extract_string_max_length(mut self, max_length: impl Into<Option<usize>>) -> Self {
+        self.extract_string_max_length = max_length.into();
+        self
+    }
+
+    /// Set whether content longer than the `extract_string_max_length` fails with
+    /// [`crate::Error::WriteLimitReached`] instead of being truncated
+    /// Default: false
+    pub fn set_extract_string_strict(mut self, strict: bool) -> Self {
+        self.extract_string_strict = strict;
         self
     }
 
     /// Set the encoding to use for when extracting text to a stream.
-    /// Not used for extract_to_string functions.
+    /// Not used for extract_to_string functions. Markdown streams only support UTF-8, see
+    /// [`OutputFormat::Markdown`]
     /// Default: CharSet::UTF_8
     pub fn set_encoding(mut self, encoding: CharSet) -> Self {
         self.encoding = encoding;
@@ -119,96 +476,745 @@ impl Extractor {
         self
     }
 
+    /// Set the password of encrypted documents, such as password-protected PDFs and Office files.
+    /// The password is also used for the embedded documents
+    /// Default: no password
+    pub fn set_password(self, password: &str) -> Self {
+        self.set_passwords(&[password])
+    }
+
+    /// Set the candidate passwords of encrypted documents. They are tried in order for each
+    /// document, including the embedded ones, until one decrypts it. Documents that none of them
+    /// decrypts fail with [`crate::Error::EncryptedDocument`]. Trying a candidate parses the start
+    /// of the document again, put the most likely password first
+    /// Default: no password
+    pub fn set_passwords<S: AsRef<str>>(mut self, passwords: &[S]) -> Self {
+        self.passwords = Passwords(passwords.iter().map(|p| p.as_ref().to_string()).collect());
+        self
+    }
+
+    /// Set a custom [tika-config.xml](https://tika.apache.org/2.9.3/configuring.html), for
+    /// example to exclude parsers, change the order of the detectors or set the parameters of
+    /// parsers that have no setter here. The XML is loaded once and used by all the extractions
+    /// and detections of this extractor and its clones. The PDF, Office and OCR configs of the
+    /// extractor take precedence over the parameters of those parsers in the XML.
+    /// Fails with [`Error::InvalidConfig`] if the XML is not a valid tika config
+    /// Default: the default tika config
+    pub fn set_tika_config_xml(mut self, xml: &str) -> ExtractResult<Self> {
+        self.tika_config = Some(tika::load_tika_config(xml)?);
+        Ok(self)
+    }
+
+    /// Set a custom tika-config.xml from a file, see [`Extractor::set_tika_config_xml`].
+    /// Fails with [`Error::FileNotFound`] if the file does not exist, [`Error::PermissionDenied`]
+    /// or [`Error::IoError`] if it cannot be read and with [`Error::InvalidConfig`] if it is not
+    /// a valid tika config
+    /// Default: the default tika config
+    pub fn set_tika_config_file<P: AsRef<Path>>(self, path: P) -> ExtractResult<Self> {
+        let path = path.as_ref();
+        let xml = fs::read_to_string(path).map_err(|e| {
+            let context = ErrorContext {
+                message: format!("Cannot read the tika config {}: {e}", path.display()),
+                path: Some(path.display().to_string()),
+                ..Default::default()
+            };
+            match e.kind() {
+                std::io::ErrorKind::NotFound => Error::FileNotFound(context),
+                std::io::ErrorKind::PermissionDenied => Error::PermissionDenied(context),
+                _ => Error::IoError(context.message),
+            }
+        })?;
+        self.set_tika_config_xml(&xml).map_err(|e| match e {
+            Error::InvalidConfig(msg) => Error::InvalidConfig(format!("{}: {msg}", path.display())),
+            e => e,
+        })
+    }
+
+    /// Set the mime types of the documents to parse, for example `application/pdf`, or `image/*`
+    /// for all the types of a top level type. Other documents fail with [`Error::Rejected`]
+    /// before any of their content is given to a parser. Embedded documents of other types are
+    /// skipped like embedded documents that fail to parse: their content is left out and the
+    /// error is recorded as an `X-TIKA:EXCEPTION:embedded_exception` metadata. Denied types take
+    /// precedence, see [`Extractor::set_denied_mime_types`]
+    /// Default: all types are allowed
+    pub fn set_allowed_mime_types<S: AsRef<str>>(mut self, mime_types: &[S]) -> Self {
+        self.allowed_mime_types = mime_types.iter().map(|t| t.as_ref().to_string()).collect();
+        self
+    }
+
+    /// Set the mime types of the documents to never parse, with the same patterns and behaviour
+    /// as [`Extractor::set_allowed_mime_types`], for example `["image/*", "audio/*"]` to skip
+    /// the images sent to OCR and the audio files
+    /// Default: no type is denied
+    pub fn set_denied_mime_types<S: AsRef<str>>(mut self, mime_types: &[S]) -> Self {
+        self.denied_mime_types = mime_types.iter().map(|t| t.as_ref().to_string()).collect();
+        self
+    }
+
     /// Set the configuration for the parse as xml
-    pub fn set_xml_output(mut self, xml_output: bool) -> Self {
-        self.xml_output = xml_output;
+    #[deprecated(note = "use `set_output_format(OutputFormat::Xhtml)` instead")]
+    pub fn set_xml_output(self, xml_output: bool) -> Self {
+        self.set_output_format(if xml_output {
+            OutputFormat::Xhtml
+        } else {
+            OutputFormat::PlainText
+        })
+    }
+
+    /// Set the format of the extracted content, see [`OutputFormat`]. The recursive extraction
+    /// returns the text of each document for [`OutputFormat::Json`], as each document already
+    /// comes with its own metadata.
+    /// Default: OutputFormat::PlainText
+    pub fn set_output_format(mut self, output_format: OutputFormat) -> Self {
+        self.output_format = output_format;
+        self
+    }
+
+    /// Set whether to detect the languages of the extracted content. When enabled, the metadata
+    /// returned by the `*_to_string` and recursive functions has the detected languages, most
+    /// probable first, under `X-TIKA:detected_language` and their confidence under
+    /// `X-TIKA:detected_language_confidence_raw`, see [`crate::DocumentMetadata::detected_languages`].
+    /// Each [`Page`] gets the languages of its own text. Streams are not covered as their content
+    /// is only known once read, use [`Extractor::detect_languages`] on the text instead.
+    /// Detection runs on the text of the document body whatever the output format, so the tags
+    /// of XHTML and HTML, the keys of JSON and the markup of Markdown are ignored
+    /// Default: false
+    pub fn set_detect_languages(mut self, detect_languages: bool) -> Self {
+        self.detect_languages = detect_languages;
+        self
+    }
+
+    /// Set the maximum duration of a single extraction, covering the whole parsing and not only
+    /// the OCR as [`TesseractOcrConfig::set_timeout_seconds`] does. Extractions that take longer
+    /// are aborted with [`crate::Error::Timeout`]. For streams the timeout covers the parsing
+    /// running in the background, not the time spent reading.
+    /// Default: no timeout
+    pub fn set_parse_timeout(mut self, timeout: Duration) -> Self {
+        self.control.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the token that aborts the extractions of this extractor when cancelled, see
+    /// [`CancellationToken`]. Aborted extractions fail with [`crate::Error::Cancelled`]
+    pub fn set_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.control.cancellation_token = Some(token);
         self
     }
 
     /// Extracts text from a file path. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_file(&self, file_path: &str) -> ExtractResult<(StreamReader, Metadata)> {
+        self.check_stream_encoding()?;
         tika::parse_file(
             file_path,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+            &ExtractOptions::default(),
+            &self.parse_settings(),
         )
+        .map(|result| self.output_stream(result))
     }
 
     /// Extracts text from a byte buffer. Returns a tuple with stream of the extracted text and metadata.
-    /// the stream is decoded using the extractor's `encoding`
+    /// the stream is decoded using the extractor's `encoding`.
+    ///
+    /// Parsing happens in the background while the stream is read, so the buffer is copied and
+    /// the returned stream does not borrow it. Use [`Extractor::extract_vec`] to avoid the copy.
     pub fn extract_bytes(&self, buffer: &[u8]) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_bytes(
-            buffer,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+        self.extract_vec(buffer.to_vec())
+    }
+
+    /// Same as [`Extractor::extract_bytes`] with the document hints of `options`
+    pub fn extract_bytes_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        self.extract_vec_with(buffer.to_vec(), options)
+    }
+
+    /// Extracts text from an owned byte buffer. Returns a tuple with stream of the extracted text
+    /// and metadata. the stream is decoded using the extractor's `encoding`.
+    ///
+    /// The buffer is moved into the background parser and dropped once parsing is done, even
+    /// if the returned stream is dropped before being fully read.
+    pub fn extract_vec(&self, buffer: Vec<u8>) -> ExtractResult<(StreamReader, Metadata)> {
+        self.extract_vec_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_vec`] with the document hints of `options`
+    pub fn extract_vec_with(
+        &self,
+        buffer: Vec<u8>,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        self.check_stream_encoding()?;
+        tika::parse_reader(
+            Box::new(Cursor::new(buffer)),
+            options,
+            &self.parse_settings(),
         )
+        .map(|result| self.output_stream(result))
     }
 
     /// Extracts text from an url. Returns a tuple with stream of the extracted text and metadata.
     /// the stream is decoded using the extractor's `encoding`
     pub fn extract_url(&self, url: &str) -> ExtractResult<(StreamReader, Metadata)> {
-        tika::parse_url(
-            url,
-            &self.encoding,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
-        )
+        self.extract_url_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url`] with the document hints of `options`
+    pub fn extract_url_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        self.check_stream_encoding()?;
+        tika::parse_url(url, options, &self.parse_settings())
+            .map(|result| self.output_stream(result))
+    }
+
+    /// Extracts text from any reader. Returns a tuple with stream of the extracted text and metadata.
+    /// the stream is decoded using the extractor's `encoding`.
+    ///
+    /// The input is not buffered in memory: it is pulled from the reader in chunks while the
+    /// document is being parsed. Parsing happens in the background, so the reader is moved into
+    /// the returned stream and dropped once parsing is done.
+    pub fn extract_reader<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        self.extract_reader_with(reader, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_reader`] with the document hints of `options`
+    pub fn extract_reader_with<R: Read + Send + 'static>(
+        &self,
+        reader: R,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        self.check_stream_encoding()?;
+        tika::parse_reader(Box::new(reader), options, &self.parse_settings())
+            .map(|result| self.output_stream(result))
     }
 
-    /// Extracts text from a file path. Returns a tuple with string that is of maximum length
-    /// of the extractor's `extract_string_max_length` and metadata.
-    pub fn extract_file_to_string(&self, file_path: &str) -> ExtractResult<(String, Metadata)> {
+    /// Extracts text from a file path. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub fn extract_file_to_string(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
         tika::parse_file_to_string(
             file_path,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+            &ExtractOptions::default(),
+            &self.parse_settings(),
+        )
+        .and_then(|result| self.output_string(result))
+    }
+
+    /// Extracts text from a byte buffer. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub fn extract_bytes_to_string(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        self.extract_bytes_to_string_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_string`] with the document hints of `options`
+    pub fn extract_bytes_to_string_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        tika::parse_bytes_to_string(buffer, options, &self.parse_settings())
+            .and_then(|result| self.output_string(result))
+    }
+
+    /// Extracts text from a URL. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    pub fn extract_url_to_string(&self, url: &str) -> ExtractResult<(ExtractedText, Metadata)> {
+        self.extract_url_to_string_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_to_string`] with the document hints of `options`
+    pub fn extract_url_to_string_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        tika::parse_url_to_string(url, options, &self.parse_settings())
+            .and_then(|result| self.output_string(result))
+    }
+
+    /// Extracts text from any reader. Returns a tuple with the [`ExtractedText`], of maximum
+    /// length the extractor's `extract_string_max_length`, and metadata.
+    ///
+    /// The input is not buffered in memory: it is pulled from the reader in chunks while the
+    /// document is being parsed.
+    pub fn extract_reader_to_string<R: Read + Send>(
+        &self,
+        reader: R,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        self.extract_reader_to_string_with(reader, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_reader_to_string`] with the document hints of `options`
+    pub fn extract_reader_to_string_with<R: Read + Send>(
+        &self,
+        reader: R,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        tika::parse_reader_to_string(Box::new(reader), options, &self.parse_settings())
+            .and_then(|result| self.output_string(result))
+    }
+
+    /// Extracts the text of each page of a file. Returns a tuple with the pages, in order, and
+    /// metadata. See [`PageIter`] for the formats with page breaks.
+    ///
+    /// Unlike the `*_to_string` functions, the text is not limited to the extractor's
+    /// `extract_string_max_length`. Use [`Extractor::extract_file_to_page_iter`] to read the
+    /// pages one at a time.
+    pub fn extract_file_to_pages(&self, file_path: &str) -> ExtractResult<(Vec<Page>, Metadata)> {
+        let (pages, metadata) = self.extract_file_to_page_iter(file_path)?;
+        Ok((pages.collect::<ExtractResult<_>>()?, metadata))
+    }
+
+    /// Extracts the text of each page of a byte buffer. Returns a tuple with the pages, in order,
+    /// and metadata. See [`Extractor::extract_file_to_pages`].
+    pub fn extract_bytes_to_pages(&self, buffer: &[u8]) -> ExtractResult<(Vec<Page>, Metadata)> {
+        self.extract_bytes_to_pages_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_pages`] with the document hints of `options`
+    pub fn extract_bytes_to_pages_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Page>, Metadata)> {
+        let (pages, metadata) = self.extract_bytes_to_page_iter_with(buffer, options)?;
+        Ok((pages.collect::<ExtractResult<_>>()?, metadata))
+    }
+
+    /// Extracts the text of each page of an url. Returns a tuple with the pages, in order, and
+    /// metadata. See [`Extractor::extract_file_to_pages`].
+    pub fn extract_url_to_pages(&self, url: &str) -> ExtractResult<(Vec<Page>, Metadata)> {
+        self.extract_url_to_pages_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_to_pages`] with the document hints of `options`
+    pub fn extract_url_to_pages_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Page>, Metadata)> {
+        let (pages, metadata) = self.extract_url_to_page_iter_with(url, options)?;
+        Ok((pages.collect::<ExtractResult<_>>()?, metadata))
+    }
+
+    /// Extracts the pages of a file. Returns a tuple with an iterator yielding each page as soon
+    /// as it is parsed and metadata.
+    pub fn extract_file_to_page_iter(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(PageIter, Metadata)> {
+        let (xhtml, metadata) = self.extract_file_to_xhtml(file_path)?;
+        Ok((PageIter::new(xhtml, self.detect_languages), metadata))
+    }
+
+    /// Extracts the pages of a byte buffer. Returns a tuple with an iterator yielding each page
+    /// as soon as it is parsed and metadata. As for [`Extractor::extract_bytes`], the buffer is
+    /// copied and the iterator does not borrow it.
+    pub fn extract_bytes_to_page_iter(&self, buffer: &[u8]) -> ExtractResult<(PageIter, Metadata)> {
+        self.extract_bytes_to_page_iter_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_page_iter`] with the document hints of `options`
+    pub fn extract_bytes_to_page_iter_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(PageIter, Metadata)> {
+        let (xhtml, metadata) = self.extract_bytes_to_xhtml(buffer, options)?;
+        Ok((PageIter::new(xhtml, self.detect_languages), metadata))
+    }
+
+    /// Extracts the pages of an url. Returns a tuple with an iterator yielding each page as soon
+    /// as it is parsed and metadata.
+    pub fn extract_url_to_page_iter(&self, url: &str) -> ExtractResult<(PageIter, Metadata)> {
+        self.extract_url_to_page_iter_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_to_page_iter`] with the document hints of `options`
+    pub fn extract_url_to_page_iter_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(PageIter, Metadata)> {
+        let (xhtml, metadata) = self.extract_url_to_xhtml(url, options)?;
+        Ok((PageIter::new(xhtml, self.detect_languages), metadata))
+    }
+
+    /// Extracts the content elements of a file: headings, paragraphs, list items, tables ...
+    /// Returns a tuple with the elements in document order and metadata.
+    ///
+    /// The elements are taken from the structure of the XHTML output of Tika, so their kinds
+    /// depend on the format. For example PDF has no headings and plain text has paragraphs only.
+    /// As for the pages, the text is not limited to the extractor's `extract_string_max_length`.
+    pub fn extract_file_to_elements(
+        &self,
+        file_path: &str,
+    ) -> ExtractResult<(Vec<Element>, Metadata)> {
+        let (xhtml, metadata) = self.extract_file_to_xhtml(file_path)?;
+        Ok((parse_elements(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts the content elements of a byte buffer. Returns a tuple with the elements in
+    /// document order and metadata. See [`Extractor::extract_file_to_elements`].
+    pub fn extract_bytes_to_elements(
+        &self,
+        buffer: &[u8],
+    ) -> ExtractResult<(Vec<Element>, Metadata)> {
+        self.extract_bytes_to_elements_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_elements`] with the document hints of `options`
+    pub fn extract_bytes_to_elements_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Element>, Metadata)> {
+        let (xhtml, metadata) = self.extract_bytes_to_xhtml(buffer, options)?;
+        Ok((parse_elements(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts the content elements of an url. Returns a tuple with the elements in document
+    /// order and metadata. See [`Extractor::extract_file_to_elements`].
+    pub fn extract_url_to_elements(&self, url: &str) -> ExtractResult<(Vec<Element>, Metadata)> {
+        self.extract_url_to_elements_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_to_elements`] with the document hints of `options`
+    pub fn extract_url_to_elements_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Element>, Metadata)> {
+        let (xhtml, metadata) = self.extract_url_to_xhtml(url, options)?;
+        Ok((parse_elements(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts the tables of a file, with their cells and spans. Returns a tuple with the tables
+    /// in document order and metadata.
+    ///
+    /// Tables are taken from the XHTML output of Tika: spreadsheet sheets, csv, office, ODF and
+    /// html tables. The spans are those Tika reports, some parsers repeat or drop merged cells.
+    pub fn extract_file_to_tables(&self, file_path: &str) -> ExtractResult<(Vec<Table>, Metadata)> {
+        let (xhtml, metadata) = self.extract_file_to_xhtml(file_path)?;
+        Ok((parse_tables(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts the tables of a byte buffer. Returns a tuple with the tables in document order
+    /// and metadata. See [`Extractor::extract_file_to_tables`].
+    pub fn extract_bytes_to_tables(&self, buffer: &[u8]) -> ExtractResult<(Vec<Table>, Metadata)> {
+        self.extract_bytes_to_tables_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_to_tables`] with the document hints of `options`
+    pub fn extract_bytes_to_tables_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Table>, Metadata)> {
+        let (xhtml, metadata) = self.extract_bytes_to_xhtml(buffer, options)?;
+        Ok((parse_tables(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts the tables of an url. Returns a tuple with the tables in document order and
+    /// metadata. See [`Extractor::extract_file_to_tables`].
+    pub fn extract_url_to_tables(&self, url: &str) -> ExtractResult<(Vec<Table>, Metadata)> {
+        self.extract_url_to_tables_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_to_tables`] with the document hints of `options`
+    pub fn extract_url_to_tables_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(Vec<Table>, Metadata)> {
+        let (xhtml, metadata) = self.extract_url_to_xhtml(url, options)?;
+        Ok((parse_tables(BufReader::new(xhtml))?, metadata))
+    }
+
+    /// Extracts text from many sources in parallel. Returns an iterator with one [`crate::BatchItem`]
+    /// per source, in completion or input order depending on `order`.
+    ///
+    /// At most `max_concurrency` extractions run at the same time, each one with this
+    /// extractor's configuration as in [`Extractor::extract_file_to_string`] and friends.
+    /// A failing source does not abort the batch, its error is returned in its item.
+    /// ```no_run
+    /// use extractous::{BatchOrder, Extractor};
+    ///
+    /// let extractor = Extractor::new();
+    /// let files = vec!["README.md", "Cargo.toml"];
+    /// for item in extractor.extract_many(files, 4, BatchOrder::Completion) {
+    ///     match item.result {
+    ///         Ok((content, _metadata)) => println!("{}: {} chars", item.source, content.len()),
+    ///         Err(e) => println!("{} failed after {:?}: {}", item.source, item.duration, e),
+    ///     }
+    /// }
+    /// ```
+    pub fn extract_many<I>(
+        &self,
+        sources: I,
+        max_concurrency: usize,
+        order: BatchOrder,
+    ) -> BatchIter<I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: Into<BatchSource>,
+    {
+        BatchIter::new(self, sources.into_iter(), max_concurrency, order)
+    }
+
+    /// Extracts text from a file path and all of its embedded documents. Returns one
+    /// [`ExtractedDocument`] per document, the container document first. The content of every
+    /// document is of maximum length of the extractor's `extract_string_max_length`.
+    pub fn extract_file_recursive(&self, file_path: &str) -> ExtractResult<Vec<ExtractedDocument>> {
+        tika::parse_file_recursive(
+            file_path,
+            &ExtractOptions::default(),
+            &self.parse_settings(),
+        )
+        .and_then(|documents| self.output_documents(documents))
+    }
+
+    /// Extracts text from a byte buffer and all of its embedded documents. Returns one
+    /// [`ExtractedDocument`] per document, the container document first. The content of every
+    /// document is of maximum length of the extractor's `extract_string_max_length`.
+    pub fn extract_bytes_recursive(&self, buffer: &[u8]) -> ExtractResult<Vec<ExtractedDocument>> {
+        self.extract_bytes_recursive_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_bytes_recursive`] with the document hints of `options`
+    pub fn extract_bytes_recursive_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<Vec<ExtractedDocument>> {
+        tika::parse_bytes_recursive(buffer, options, &self.parse_settings())
+            .and_then(|documents| self.output_documents(documents))
+    }
+
+    /// Extracts text from an url and all of its embedded documents. Returns one
+    /// [`ExtractedDocument`] per document, the container document first. The content of every
+    /// document is of maximum length of the extractor's `extract_string_max_length`.
+    pub fn extract_url_recursive(&self, url: &str) -> ExtractResult<Vec<ExtractedDocument>> {
+        self.extract_url_recursive_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::extract_url_recursive`] with the document hints of `options`
+    pub fn extract_url_recursive_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<Vec<ExtractedDocument>> {
+        tika::parse_url_recursive(url, options, &self.parse_settings())
+            .and_then(|documents| self.output_documents(documents))
+    }
+
+    /// Returns the settings of the tika parse calls
+    fn parse_settings(&self) -> tika::ParseSettings<'_> {
+        tika::ParseSettings {
+            char_set: self.encoding.clone(),
+            max_length: self.tika_max_length(),
+            pdf_conf: &self.pdf_config,
+            office_conf: &self.office_config,
+            ocr_conf: &self.ocr_config,
+            passwords: &self.passwords.0,
+            tika_config: self.tika_config.as_ref(),
+            allowed_mime_types: &self.allowed_mime_types,
+            denied_mime_types: &self.denied_mime_types,
+            output_format: self.tika_output_format(),
+            detect_languages: self.detect_languages,
+            control: &self.control,
+        }
+    }
+
+    /// Returns the settings of the parse calls to an UTF-8 stream of XHTML
+    fn xhtml_parse_settings(&self) -> tika::ParseSettings<'_> {
+        tika::ParseSettings {
+            char_set: CharSet::UTF_8,
+            output_format: OutputFormat::Xhtml,
+            // The pages detect the languages of their own text
+            detect_languages: false,
+            ..self.parse_settings()
+        }
+    }
+
+    /// Returns the maximum length of the strings for the java calls, -1 for no limit
+    fn tika_max_length(&self) -> i32 {
+        self.extract_string_max_length
+            .map_or(-1, |max_length| max_length.min(i32::MAX as usize) as i32)
+    }
+
+    /// Returns the format of the Tika output, XHTML is the input of the Markdown conversion
+    fn tika_output_format(&self) -> OutputFormat {
+        match self.output_format {
+            OutputFormat::Markdown => OutputFormat::Xhtml,
+            format => format,
+        }
+    }
+
+    /// Fails with [`Error::UnsupportedEncoding`] for Markdown streams in another encoding than
+    /// UTF-8, the Markdown conversion reads and writes UTF-8 only
+    fn check_stream_encoding(&self) -> ExtractResult<()> {
+        if self.output_format != OutputFormat::Markdown || self.encoding == CharSet::UTF_8 {
+            return Ok(());
+        }
+        Err(Error::UnsupportedEncoding(format!(
+            "Markdown streams are UTF-8 only, got the {} encoding",
+            self.encoding
+        )))
+    }
+
+    fn output_stream(
+        &self,
+        (reader, metadata): (StreamReader, Metadata),
+    ) -> (StreamReader, Metadata) {
+        if self.output_format == OutputFormat::Markdown {
+            (reader.into_markdown(), metadata)
+        } else {
+            (reader, metadata)
+        }
+    }
+
+    fn output_string(
+        &self,
+        (content, metadata): (String, Metadata),
+    ) -> ExtractResult<(ExtractedText, Metadata)> {
+        self.check_truncation(&metadata)?;
+        let content = if self.output_format == OutputFormat::Markdown {
+            xhtml_to_markdown(&content)?
+        } else {
+            content
+        };
+        let text = ExtractedText {
+            content,
+            truncated: is_truncated(&metadata),
+        };
+        Ok((text, metadata))
+    }
+
+    fn output_documents(
+        &self,
+        mut documents: Vec<ExtractedDocument>,
+    ) -> ExtractResult<Vec<ExtractedDocument>> {
+        for document in &mut documents {
+            self.check_truncation(&document.metadata)?;
+            if self.output_format == OutputFormat::Markdown {
+                document.content = xhtml_to_markdown(&document.content)?;
+            }
+        }
+        Ok(documents)
+    }
+
+    /// Fails with [`Error::WriteLimitReached`] for truncated content in strict mode
+    fn check_truncation(&self, metadata: &Metadata) -> ExtractResult<()> {
+        if !self.extract_string_strict || !is_truncated(metadata) {
+            return Ok(());
+        }
+        Err(Error::WriteLimitReached(ErrorContext {
+            message: format!(
+                "The content is longer than the maximum length of {} characters",
+                self.tika_max_length()
+            ),
+            mime_type: metadata
+                .get("Content-Type")
+                .and_then(|values| values.first())
+                .cloned(),
+            ..Default::default()
+        }))
+    }
+
+    /// Extracts a file to an UTF-8 stream of XHTML, the structured output the pages and the
+    /// elements are built from
+    fn extract_file_to_xhtml(&self, file_path: &str) -> ExtractResult<(StreamReader, Metadata)> {
+        tika::parse_file(
+            file_path,
+            &ExtractOptions::default(),
+            &self.xhtml_parse_settings(),
         )
     }
 
-    /// Extracts text from a byte buffer. Returns a tuple with string that is of maximum length
-    /// of the extractor's `extract_string_max_length` and metadata.
-    pub fn extract_bytes_to_string(&self, buffer: &[u8]) -> ExtractResult<(String, Metadata)> {
-        tika::parse_bytes_to_string(
-            buffer,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+    /// Extracts a copy of a byte buffer to an UTF-8 stream of XHTML
+    fn extract_bytes_to_xhtml(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        tika::parse_reader(
+            Box::new(Cursor::new(buffer.to_vec())),
+            options,
+            &self.xhtml_parse_settings(),
         )
     }
 
-    /// Extracts text from a URL. Returns a tuple with string that is of maximum length
-    /// of the extractor's `extract_string_max_length` and metadata.
-    pub fn extract_url_to_string(&self, url: &str) -> ExtractResult<(String, Metadata)> {
-        tika::parse_url_to_string(
-            url,
-            self.extract_string_max_length,
-            &self.pdf_config,
-            &self.office_config,
-            &self.ocr_config,
-            self.xml_output,
+    /// Extracts an url to an UTF-8 stream of XHTML
+    fn extract_url_to_xhtml(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<(StreamReader, Metadata)> {
+        tika::parse_url(url, options, &self.xhtml_parse_settings())
+    }
+
+    /// Detects the mime type of a file without extracting its content
+    pub fn detect_file(&self, file_path: &str) -> ExtractResult<DetectResult> {
+        tika::detect_file(
+            file_path,
+            &ExtractOptions::default(),
+            self.tika_config.as_ref(),
         )
     }
 
+    /// Detects the mime type of a byte buffer without extracting its content
+    pub fn detect_bytes(&self, buffer: &[u8]) -> ExtractResult<DetectResult> {
+        self.detect_bytes_with(buffer, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::detect_bytes`] with the document hints of `options`
+    pub fn detect_bytes_with(
+        &self,
+        buffer: &[u8],
+        options: &ExtractOptions,
+    ) -> ExtractResult<DetectResult> {
+        tika::detect_bytes(buffer, options, self.tika_config.as_ref())
+    }
+
+    /// Detects the mime type of an url without extracting its content
+    pub fn detect_url(&self, url: &str) -> ExtractResult<DetectResult> {
+        self.detect_url_with(url, &ExtractOptions::default())
+    }
+
+    /// Same as [`Extractor::detect_url`] with the document hints of `options`
+    pub fn detect_url_with(
+        &self,
+        url: &str,
+        options: &ExtractOptions,
+    ) -> ExtractResult<DetectResult> {
+        tika::detect_url(url, options, self.tika_config.as_ref())
+    }
+
+    /// Detects the languages of a text, for example of a [`crate::Chunk`]. Returns the languages
+    /// found, most probable first. Short texts may not have any language
+    pub fn detect_languages(&self, text: &str) -> ExtractResult<Vec<DetectedLanguage>> {
+        tika::detect_languages(text)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::StreamReader;
-    use crate::Extractor;
+    use crate::{CharSet, Extractor, OutputFormat};
     use std::fs::File;
     use std::io::BufReader;
     use std::io::{self, Read};
@@ -309,10 +1315,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn detect_file_test() {
+        let extractor = Extractor::new();
+        let result = extractor.detect_file(TEST_FILE).unwrap();
+
+        assert_eq!(result.mime_type, "text/x-web-markdown");
+        assert!(
+            !result.metadata.is_empty(),
+            "Metadata should contain at least one entry"
+        );
+    }
+
     #[test]
     fn extract_file_to_xml_test() {
         // Parse the files using extractous
-        let extractor = Extractor::new().set_xml_output(true);
+        let extractor = Extractor::new().set_output_format(OutputFormat::Xhtml);
         let result = extractor.extract_file_to_string(TEST_FILE);
         let (content, metadata) = result.unwrap();
         assert!(
@@ -324,4 +1342,26 @@ mod tests {
             "Metadata should contain at least one entry"
         );
     }
+
+    #[test]
+    fn charset_names_test() {
+        assert_eq!(CharSet::UTF_8.to_string(), "UTF-8");
+        assert_eq!(CharSet::WINDOWS_1252.to_string(), "windows-1252");
+        assert_eq!(CharSet::SHIFT_JIS.to_string(), "Shift_JIS");
+        assert_eq!(
+            CharSet::Custom("windows-1251".to_string()).to_string(),
+            "windows-1251"
+        );
+
+        // Both the variant and the Java names are parsed, other names are custom
+        assert_eq!("US_ASCII".parse::<CharSet>().unwrap(), CharSet::US_ASCII);
+        assert_eq!(
+            "ISO-8859-1".parse::<CharSet>().unwrap(),
+            CharSet::ISO_8859_1
+        );
+        assert_eq!(
+            "cp1251".parse::<CharSet>().unwrap(),
+            CharSet::Custom("cp1251".to_string())
+        );
+    }
 }
@@ -0,0 +1,75 @@
+use crate::Metadata;
+
+// Tika keys of the detected languages, the same as the ones set by Tika's own language detection.
+// Set by the java LanguageSampleHandler with one value per language
+pub(crate) const DETECTED_LANGUAGE_KEY: &str = "X-TIKA:detected_language";
+pub(crate) const DETECTED_LANGUAGE_CONFIDENCE_KEY: &str = "X-TIKA:detected_language_confidence_raw";
+
+/// A language detected in the extracted text, see [`crate::Extractor::detect_languages`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectedLanguage {
+    /// The ISO 639-1 code of the language, for example `en` or `de`
+    pub language: String,
+    /// The probability of the language, between 0 and 1
+    pub confidence: f32,
+}
+
+/// Reads back the detected languages from the metadata. Languages without a valid confidence
+/// are skipped
+pub(crate) fn languages_from_metadata(metadata: &Metadata) -> Vec<DetectedLanguage> {
+    let (Some(languages), Some(confidences)) = (
+        metadata.get(DETECTED_LANGUAGE_KEY),
+        metadata.get(DETECTED_LANGUAGE_CONFIDENCE_KEY),
+    ) else {
+        return Vec::new();
+    };
+
+    languages
+        .iter()
+        .zip(confidences)
+        .filter_map(|(language, confidence)| {
+            Some(DetectedLanguage {
+                language: language.trim().to_string(),
+                confidence: confidence.trim().parse().ok()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn languages_from_metadata_test() {
+        let languages = vec![
+            DetectedLanguage {
+                language: "de".to_string(),
+                confidence: 0.75,
+            },
+            DetectedLanguage {
+                language: "en".to_string(),
+                confidence: 0.25,
+            },
+        ];
+
+        // As set by the java side, with the raw float representation of java
+        let mut metadata = Metadata::new();
+        metadata.insert(
+            DETECTED_LANGUAGE_KEY.to_string(),
+            vec!["de".to_string(), "en".to_string()],
+        );
+        metadata.insert(
+            DETECTED_LANGUAGE_CONFIDENCE_KEY.to_string(),
+            vec!["0.75".to_string(), "2.5E-1".to_string()],
+        );
+        assert_eq!(languages_from_metadata(&metadata), languages);
+
+        metadata.insert(
+            DETECTED_LANGUAGE_CONFIDENCE_KEY.to_string(),
+            vec!["high".to_string(), "0.25".to_string()],
+        );
+        assert_eq!(languages_from_metadata(&metadata), languages[1..]);
+        assert_eq!(languages_from_metadata(&Metadata::new()), vec![]);
+    }
+}
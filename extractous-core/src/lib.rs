@@ -48,7 +48,7 @@
 //!
 //! ## Extract text with OCR
 //! * Make sure Tesseract is installed with the corresponding language packs. For example on debian `sudo apt install tesseract-ocr tesseract-ocr-deu` to install tesseract with German language pack.
-//! * If you get `Parse error occurred : Unable to extract PDF content`, it is most likely that the OCR language pack is not installed
+//! * If you get an [`Error::OcrFailure`], it is most likely that the OCR language pack is not installed
 //!
 //! ```no_run
 //! use extractous::{Extractor, TesseractOcrConfig, PdfParserConfig, PdfOcrStrategy};
@@ -79,12 +79,50 @@ pub use config::*;
 // extractor module is the main public api interface
 mod extractor;
 pub use extractor::*;
+// batch module is the parallel extraction interface
+mod batch;
+pub use batch::*;
+// pool of threads for the blocking extraction calls
+mod blocking_pool;
+// cancellation module is the timeout and cancellation interface
+mod cancellation;
+pub use cancellation::*;
+// document_metadata module is the typed metadata interface
+mod document_metadata;
+pub use document_metadata::*;
+// pages module is the per page extraction interface
+mod pages;
+pub use pages::*;
+// elements module is the typed content elements interface
+mod elements;
+pub use elements::*;
+// tables module is the structured tables interface
+mod tables;
+pub use tables::*;
+// language module is the language detection interface
+mod language;
+pub use language::*;
+// chunker module is the interface splitting extracted text into chunks
+mod chunker;
+pub use chunker::*;
+// reader of the xhtml output of tika
+mod xhtml;
+// conversion of the xhtml output of tika to markdown
+mod markdown;
+
+// async_extractor module is the async api interface, enabled with the async feature
+#[cfg(feature = "async")]
+mod async_extractor;
+#[cfg(feature = "async")]
+pub use async_extractor::*;
 
 // tika module, not exposed outside this crate
 mod tika {
     mod jni_utils;
     mod parse;
+    mod reader_source;
     mod wrappers;
     pub use parse::*;
-    pub use wrappers::JReaderInputStream;
+    pub(crate) use wrappers::cancel_parse_monitor;
+    pub use wrappers::{JReaderInputStream, JTikaConfig};
 }
@@ -0,0 +1,424 @@
+use crate::errors::ExtractResult;
+use crate::tables::{grid, TableBuilder};
+use crate::xhtml::{XhtmlEvent, XhtmlReader};
+use std::io::{self, BufRead, Read};
+
+/// Converts the XHTML output of Tika to Markdown
+pub(crate) fn xhtml_to_markdown(xhtml: &str) -> ExtractResult<String> {
+    let mut reader = XhtmlReader::new(xhtml.as_bytes());
+    let mut writer = MarkdownWriter::default();
+    while let Some(event) = reader.next_event()? {
+        writer.handle(event);
+    }
+    writer.finish();
+    Ok(writer.out)
+}
+
+/// Reader converting a stream of Tika XHTML to a stream of UTF-8 Markdown while it is read
+pub(crate) struct MarkdownReader<R: BufRead> {
+    xhtml: XhtmlReader<R>,
+    writer: MarkdownWriter,
+    /// Markdown ready to be read
+    ready: String,
+    pos: usize,
+    done: bool,
+}
+
+impl<R: BufRead> MarkdownReader<R> {
+    pub(crate) fn new(xhtml: R) -> Self {
+        Self {
+            xhtml: XhtmlReader::new(xhtml),
+            writer: MarkdownWriter::default(),
+            ready: String::new(),
+            pos: 0,
+            done: false,
+        }
+    }
+
+    /// Returns the reader of the XHTML
+    pub(crate) fn get_ref(&self) -> &R {
+        self.xhtml.get_ref()
+    }
+}
+
+impl<R: BufRead> Read for MarkdownReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.pos >= self.ready.len() {
+            if self.done {
+                return Ok(0);
+            }
+            match self.xhtml.next_event()? {
+                Some(event) => self.writer.handle(event),
+                None => {
+                    self.writer.finish();
+                    self.done = true;
+                }
+            }
+            self.ready = self.writer.take_ready(self.done);
+            self.pos = 0;
+        }
+
+        let available = &self.ready.as_bytes()[self.pos..];
+        let len = available.len().min(buf.len());
+        buf[..len].copy_from_slice(&available[..len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// Renders the XHTML events as Markdown
+#[derive(Default)]
+struct MarkdownWriter {
+    /// Markdown not yet taken by [`MarkdownWriter::take_ready`]
+    out: String,
+    /// Number of bytes already taken
+    taken: usize,
+    /// The open lists, with the next number of ordered lists
+    lists: Vec<Option<usize>>,
+    /// The open emphasis and link elements, with the position of their opening marker
+    inlines: Vec<Inline>,
+    /// The table being read, with the depth of the tables nested in it
+    table: Option<(TableBuilder, usize)>,
+    /// Depth of the open `pre` elements
+    pre: usize,
+    /// Whitespace was skipped since the last written text
+    pending_space: bool,
+    /// Element depth in the body
+    depth: usize,
+    /// Depth of the outermost embedded document element being read
+    embedded_depth: Option<usize>,
+    pages: usize,
+}
+
+enum Inline {
+    Emphasis {
+        marker: &'static str,
+        start: usize,
+    },
+    Link {
+        href: String,
+        start: usize,
+    },
+    /// Element without Markdown rendering, e.g. an anchor without href
+    Plain,
+}
+
+impl MarkdownWriter {
+    fn handle(&mut self, event: XhtmlEvent) {
+        match event {
+            XhtmlEvent::Start { ref name, .. } => {
+                self.depth += 1;
+                if let Some((table, nested)) = &mut self.table {
+                    if name == "table" {
+                        *nested += 1;
+                    }
+                    table.start(&event);
+                    return;
+                }
+
+                if self.embedded_depth.is_none() && event.is_embedded_start() {
+                    self.embedded_depth = Some(self.depth);
+                } else if self.embedded_depth.is_none() && event.is_page_start() {
+                    self.pages += 1;
+                    if self.pages > 1 {
+                        self.block_break();
+                        self.out.push_str("---");
+                        self.block_break();
+                    }
+                    return;
+                }
+                self.start(name, &event);
+            }
+            XhtmlEvent::End { ref name } => {
+                if let Some((table, nested)) = &mut self.table {
+                    if name != "table" || *nested > 0 {
+                        if name == "table" {
+                            *nested -= 1;
+                        }
+                        table.end(name);
+                        self.depth -= 1;
+                        return;
+                    }
+                    self.write_table();
+                } else {
+                    self.end(name);
+                }
+
+                if self.embedded_depth == Some(self.depth) {
+                    self.embedded_depth = None;
+                }
+                self.depth = self.depth.saturating_sub(1);
+            }
+            XhtmlEvent::Text(text) => match &mut self.table {
+                Some((table, _)) => table.text(&text),
+                None => self.text(&text),
+            },
+        }
+    }
+
+    fn start(&mut self, name: &str, event: &XhtmlEvent) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block_break();
+                let level = name[1..].parse().unwrap_or(1);
+                self.out.push_str(&"#".repeat(level));
+                self.out.push(' ');
+            }
+            "p" | "div" | "blockquote" if self.lists.is_empty() => self.block_break(),
+            "ul" | "ol" => {
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+                self.lists.push((name == "ol").then_some(1));
+            }
+            "li" => {
+                self.line_break();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                self.out.push_str(&indent);
+                match self.lists.last_mut() {
+                    Some(Some(number)) => {
+                        self.out.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => self.out.push_str("- "),
+                }
+            }
+            "b" | "strong" => self.open_emphasis("**"),
+            "i" | "em" => self.open_emphasis("*"),
+            "a" => match event.attribute("href") {
+                Some(href) if !href.is_empty() && !href.starts_with('#') => {
+                    self.write_pending_space();
+                    let start = self.out.len() + self.taken;
+                    self.out.push('[');
+                    self.inlines.push(Inline::Link {
+                        href: href.to_string(),
+                        start,
+                    });
+                }
+                _ => self.inlines.push(Inline::Plain),
+            },
+            "br" => {
+                if self.pre == 0 {
+                    self.out.push_str("  ");
+                }
+                self.out.push('\n');
+                self.pending_space = false;
+            }
+            "pre" => {
+                self.block_break();
+                self.out.push_str("```\n");
+                self.pre += 1;
+            }
+            "table" => {
+                self.block_break();
+                self.table = Some((TableBuilder::default(), 0));
+            }
+            _ => {}
+        }
+    }
+
+    fn end(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => self.block_break(),
+            "p" | "div" | "blockquote" if self.lists.is_empty() => self.block_break(),
+            "ul" | "ol" => {
+                self.lists.pop();
+                if self.lists.is_empty() {
+                    self.block_break();
+                }
+            }
+            "b" | "strong" | "i" | "em" | "a" => self.close_inline(),
+            "pre" => {
+                self.pre = self.pre.saturating_sub(1);
+                self.line_break();
+                self.out.push_str("```");
+                self.block_break();
+            }
+            _ => {}
+        }
+    }
+
+    fn text(&mut self, text: &str) {
+        if self.pre > 0 {
+            self.out.push_str(text);
+            return;
+        }
+        // Whitespace is collapsed, the line breaks come from the elements
+        for c in text.chars() {
+            if c.is_whitespace() {
+                self.pending_space = true;
+            } else {
+                self.write_pending_space();
+                self.out.push(c);
+            }
+        }
+    }
+
+    fn write_pending_space(&mut self) {
+        if self.pending_space && !self.out.ends_with(char::is_whitespace) && !self.at_start() {
+            self.out.push(' ');
+        }
+        self.pending_space = false;
+    }
+
+    fn open_emphasis(&mut self, marker: &'static str) {
+        self.write_pending_space();
+        let start = self.out.len() + self.taken;
+        self.out.push_str(marker);
+        self.inlines.push(Inline::Emphasis { marker, start });
+    }
+
+    fn close_inline(&mut self) {
+        let (start, opening_len, closing) = match self.inlines.pop() {
+            Some(Inline::Emphasis { marker, start }) => (start, marker.len(), marker.to_string()),
+            Some(Inline::Link { href, start }) => (start, 1, format!("]({})", href)),
+            Some(Inline::Plain) | None => return,
+        };
+        // Drop the opening marker of empty elements
+        let start = start - self.taken;
+        if self.out.len() == start + opening_len {
+            self.out.truncate(start);
+        } else {
+            self.out.push_str(&closing);
+        }
+    }
+
+    fn write_table(&mut self) {
+        let Some((table, _)) = self.table.take() else {
+            return;
+        };
+        let (rows, _header_rows) = table.finish();
+        let grid = grid(&rows);
+        // GFM tables always start with a header row, the first row is used when there is none
+        for (index, row) in grid.iter().enumerate() {
+            let cells: Vec<_> = row.iter().map(|cell| table_cell(cell)).collect();
+            self.out.push_str(&format!("| {} |\n", cells.join(" | ")));
+            if index == 0 {
+                let separator = vec!["---"; row.len()];
+                self.out
+                    .push_str(&format!("| {} |\n", separator.join(" | ")));
+            }
+        }
+        self.block_break();
+    }
+
+    /// Ends the current line
+    fn line_break(&mut self) {
+        if !self.at_start() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+        self.pending_space = false;
+    }
+
+    /// Ends the current block with an empty line
+    fn block_break(&mut self) {
+        self.line_break();
+        if !self.at_start() && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    fn at_start(&self) -> bool {
+        self.taken == 0 && self.out.is_empty()
+    }
+
+    fn finish(&mut self) {
+        while !self.inlines.is_empty() {
+            self.close_inline();
+        }
+        self.write_table();
+        let len = self.out.trim_end().len();
+        self.out.truncate(len);
+        if !self.at_start() {
+            self.out.push('\n');
+        }
+    }
+
+    /// Takes the Markdown that won't change anymore. The end of the output and the open inline
+    /// elements are kept until they are complete, unless the document is done
+    fn take_ready(&mut self, done: bool) -> String {
+        let mut len = if done {
+            self.out.len()
+        } else {
+            let open = self.inlines.iter().filter_map(|inline| match inline {
+                Inline::Emphasis { start, .. } | Inline::Link { start, .. } => Some(start),
+                Inline::Plain => None,
+            });
+            // Keep the end of the output before an open element too, in case it is dropped
+            let end = self.out.len().saturating_sub(2);
+            open.map(|start| (start - self.taken).saturating_sub(2))
+                .fold(end, usize::min)
+        };
+        while !self.out.is_char_boundary(len) {
+            len -= 1;
+        }
+        self.taken += len;
+        self.out.drain(..len).collect()
+    }
+}
+
+/// Escapes the text of a GFM table cell, which must fit on a single line
+fn table_cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const XHTML: &str = r#"<html><head><title>Doc</title></head><body>
+<h1>Title</h1>
+<p>Some <b>bold</b> and <i>italic </i>text with a <a href="https://example.com">link</a>.<b></b></p>
+<ul><li>One</li>
+<li>Two<ol><li>Sub one</li>
+<li>Sub two</li></ol></li></ul>
+<table><tbody><tr><td>Name</td><td>A | B</td></tr>
+<tr><td colspan="2">Merged</td></tr></tbody></table>
+<div class="page"><p>Page one</p></div>
+<div class="page"><p>Page two</p></div>
+</body></html>"#;
+
+    const MARKDOWN: &str = "# Title
+
+Some **bold** and *italic* text with a [link](https://example.com).
+
+- One
+- Two
+  1. Sub one
+  2. Sub two
+
+| Name | A \\| B |
+| --- | --- |
+| Merged |  |
+
+Page one
+
+---
+
+Page two
+";
+
+    #[test]
+    fn xhtml_to_markdown_test() {
+        assert_eq!(xhtml_to_markdown(XHTML).unwrap(), MARKDOWN);
+    }
+
+    #[test]
+    fn markdown_reader_test() {
+        // Read in small chunks, so the output is taken while the document is converted
+        let mut reader = MarkdownReader::new(XHTML.as_bytes());
+        let mut markdown = Vec::new();
+        let mut buf = [0u8; 3];
+        loop {
+            let len = reader.read(&mut buf).unwrap();
+            if len == 0 {
+                break;
+            }
+            markdown.extend_from_slice(&buf[..len]);
+        }
+        assert_eq!(String::from_utf8(markdown).unwrap(), MARKDOWN);
+    }
+}
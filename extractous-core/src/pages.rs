@@ -0,0 +1,221 @@
+use crate::errors::ExtractResult;
+use crate::tika;
+use crate::xhtml::{XhtmlEvent, XhtmlReader};
+use crate::{DetectedLanguage, StreamReader};
+use std::io::{BufRead, BufReader};
+
+/// A page of an extracted document
+#[derive(Debug, Clone, PartialEq)]
+pub struct Page {
+    /// The page number, starting at 1
+    pub number: usize,
+    /// The text of the page, without surrounding whitespace
+    pub text: String,
+    /// The languages of the text, most probable first. Only detected when enabled with
+    /// [`crate::Extractor::set_detect_languages`]
+    pub languages: Vec<DetectedLanguage>,
+}
+
+/// Iterator over the pages of a document, returned by [`crate::Extractor::extract_file_to_page_iter`]
+/// and friends. Pages are yielded as soon as they are parsed, so the whole document is never held
+/// in memory.
+///
+/// Page breaks are taken from the page markers Tika emits: PDF pages and PowerPoint slides.
+/// Formats without such markers, for example DOCX where pages only exist once rendered, are
+/// returned as a single page.
+pub struct PageIter {
+    splitter: PageSplitter<BufReader<StreamReader>>,
+    detect_languages: bool,
+}
+
+impl PageIter {
+    pub(crate) fn new(xhtml: StreamReader, detect_languages: bool) -> Self {
+        Self {
+            splitter: PageSplitter::new(BufReader::new(xhtml)),
+            detect_languages,
+        }
+    }
+}
+
+impl Iterator for PageIter {
+    type Item = ExtractResult<Page>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let page = self.splitter.next_page().transpose()?;
+        if !self.detect_languages {
+            return Some(page);
+        }
+        Some(page.and_then(|mut page| {
+            page.languages = tika::detect_languages(&page.text)?;
+            Ok(page)
+        }))
+    }
+}
+
+/// Splits the XHTML output of Tika into pages
+pub(crate) struct PageSplitter<R: BufRead> {
+    reader: XhtmlReader<R>,
+    text: String,
+    /// Number of the last returned page
+    number: usize,
+    /// Element depth in the body
+    depth: usize,
+    /// Depth of the page element being read
+    page_depth: Option<usize>,
+    /// Depth of the outermost embedded document element being read
+    embedded_depth: Option<usize>,
+    seen_page: bool,
+    done: bool,
+}
+
+impl<R: BufRead> PageSplitter<R> {
+    pub(crate) fn new(xhtml: R) -> Self {
+        Self {
+            reader: XhtmlReader::new(xhtml),
+            text: String::new(),
+            number: 0,
+            depth: 0,
+            page_depth: None,
+            embedded_depth: None,
+            seen_page: false,
+            done: false,
+        }
+    }
+
+    /// Returns the next page. Text before the first page marker is part of the first page, and
+    /// text between two pages is part of the previous one
+    pub(crate) fn next_page(&mut self) -> ExtractResult<Option<Page>> {
+        while !self.done {
+            let event = match self.reader.next_event() {
+                Ok(Some(event)) => event,
+                Ok(None) => {
+                    self.done = true;
+                    // Documents without page markers are a single page
+                    if self.seen_page || !self.text.trim().is_empty() {
+                        return Ok(Some(self.take_page()));
+                    }
+                    break;
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Err(e);
+                }
+            };
+
+            match event {
+                XhtmlEvent::Start { .. } => {
+                    self.depth += 1;
+                    if self.page_depth.is_some() || self.embedded_depth.is_some() {
+                        continue;
+                    }
+                    if event.is_embedded_start() {
+                        self.embedded_depth = Some(self.depth);
+                    } else if event.is_page_start() {
+                        self.page_depth = Some(self.depth);
+                        if self.seen_page {
+                            return Ok(Some(self.take_page()));
+                        }
+                        self.seen_page = true;
+                    }
+                }
+                XhtmlEvent::End { .. } => {
+                    if self.page_depth == Some(self.depth) {
+                        self.page_depth = None;
+                    }
+                    if self.embedded_depth == Some(self.depth) {
+                        self.embedded_depth = None;
+                    }
+                    self.depth = self.depth.saturating_sub(1);
+                }
+                XhtmlEvent::Text(text) => self.text.push_str(&text),
+            }
+        }
+        Ok(None)
+    }
+
+    fn take_page(&mut self) -> Page {
+        self.number += 1;
+        let text = std::mem::take(&mut self.text);
+        Page {
+            number: self.number,
+            text: text.trim().to_string(),
+            languages: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pages(xhtml: &str) -> Vec<Page> {
+        let mut splitter = PageSplitter::new(xhtml.as_bytes());
+        let mut pages = Vec::new();
+        while let Some(page) = splitter.next_page().unwrap() {
+            pages.push(page);
+        }
+        pages
+    }
+
+    fn page(number: usize, text: &str) -> Page {
+        Page {
+            number,
+            text: text.to_string(),
+            languages: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn pdf_pages_test() {
+        let xhtml = r#"<html xmlns="http://www.w3.org/1999/xhtml"><head><title>Title</title></head>
+<body><div class="page"><p>First &amp; page</p>
+</div>
+<div class="page"></div>
+<div class="page"><p>Third</p>
+<div class="annotation"><p>Note</p></div>
+</div>
+<p>Trailing</p>
+</body></html>"#;
+
+        assert_eq!(
+            pages(xhtml),
+            vec![
+                page(1, "First & page"),
+                page(2, ""),
+                page(3, "Third\nNote\n\nTrailing")
+            ]
+        );
+    }
+
+    #[test]
+    fn slides_test() {
+        let xhtml = r#"<html><body>
+<div class="slide-content"><p>Slide 1</p></div>
+<div class="slide-notes"><p>Notes 1</p></div>
+<div class="slide-content"><p>Slide 2</p></div>
+</body></html>"#;
+
+        assert_eq!(
+            pages(xhtml),
+            vec![page(1, "Slide 1\nNotes 1"), page(2, "Slide 2")]
+        );
+    }
+
+    #[test]
+    fn no_page_markers_test() {
+        let xhtml = "<html><body><p>Hello</p><br/><p>World</p></body></html>";
+        assert_eq!(pages(xhtml), vec![page(1, "HelloWorld")]);
+
+        assert_eq!(pages("<html><body>  </body></html>"), vec![]);
+    }
+
+    #[test]
+    fn embedded_pages_test() {
+        let xhtml = r#"<html><body>
+<div class="page"><p>Container</p></div>
+<div class="embedded"><div class="page"><p>Attachment</p></div></div>
+</body></html>"#;
+
+        assert_eq!(pages(xhtml), vec![page(1, "Container\nAttachment")]);
+    }
+}
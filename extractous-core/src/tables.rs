@@ -0,0 +1,267 @@
+use crate::xhtml::XhtmlEvent;
+use std::collections::HashSet;
+
+/// A table of a document, see [`crate::Extractor::extract_file_to_tables`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    /// The rows of the table, each with its cells in column order. A cell spanning several rows
+    /// is only part of its first row
+    pub rows: Vec<Vec<TableCell>>,
+    /// The number of header rows at the top of the table
+    pub header_rows: usize,
+    /// The number of the page, slide or spreadsheet sheet holding the table, starting at 1.
+    /// `None` for documents without page markers
+    pub page_number: Option<usize>,
+    /// The first heading of the page holding the table, which is the sheet name of spreadsheets
+    pub page_title: Option<String>,
+    /// The index of the table in the elements returned by
+    /// [`crate::Extractor::extract_file_to_elements`]
+    pub element_index: usize,
+}
+
+/// A cell of a [`Table`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableCell {
+    /// The text of the cell, without surrounding whitespace
+    pub text: String,
+    /// The row of the cell, starting at 0
+    pub row: usize,
+    /// The column of the cell in the table grid, starting at 0. It accounts for the cells of
+    /// the previous rows spanning over this row
+    pub column: usize,
+    pub row_span: usize,
+    pub col_span: usize,
+    /// True for `th` cells and the cells of `thead` rows
+    pub is_header: bool,
+}
+
+impl Table {
+    /// Returns the number of columns of the table grid
+    pub fn num_columns(&self) -> usize {
+        num_columns(&self.rows)
+    }
+
+    /// Returns the text of the cells as a rectangular grid, for example to load the table into
+    /// a dataframe. A merged cell is at its top left position, the other positions it spans
+    /// are empty
+    pub fn to_grid(&self) -> Vec<Vec<String>> {
+        grid(&self.rows)
+    }
+}
+
+/// Lays out the text of the cells on a rectangular grid, see [`Table::to_grid`]
+pub(crate) fn grid(rows: &[Vec<TableCell>]) -> Vec<Vec<String>> {
+    let mut grid = vec![vec![String::new(); num_columns(rows)]; rows.len()];
+    for cell in rows.iter().flatten() {
+        grid[cell.row][cell.column] = cell.text.clone();
+    }
+    grid
+}
+
+fn num_columns(rows: &[Vec<TableCell>]) -> usize {
+    rows.iter()
+        .flatten()
+        .map(|cell| cell.column + cell.col_span)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Builds the rows of a table from the XHTML events inside its `table` element
+#[derive(Debug, Default)]
+pub(crate) struct TableBuilder {
+    rows: Vec<Vec<TableCell>>,
+    /// Grid positions covered by the cells of the previous rows spanning several rows
+    covered: HashSet<(usize, usize)>,
+    /// Cell being read
+    cell: Option<TableCell>,
+    in_thead: bool,
+    /// Depth of the tables nested in the cell being read, their text is part of the cell
+    nested: usize,
+}
+
+impl TableBuilder {
+    pub(crate) fn start(&mut self, event: &XhtmlEvent) {
+        let XhtmlEvent::Start { name, .. } = event else {
+            return;
+        };
+        if self.nested > 0 || (name == "table" && self.cell.is_some()) {
+            if name == "table" {
+                self.nested += 1;
+            }
+            return;
+        }
+
+        match name.as_str() {
+            "thead" => self.in_thead = true,
+            "tr" => self.rows.push(Vec::new()),
+            "td" | "th" => {
+                if self.rows.is_empty() {
+                    self.rows.push(Vec::new());
+                }
+                let row = self.rows.len() - 1;
+                let span = |key| {
+                    event
+                        .attribute(key)
+                        .and_then(|span| span.trim().parse::<usize>().ok())
+                        .unwrap_or(1)
+                        .max(1)
+                };
+                let (row_span, col_span) = (span("rowspan"), span("colspan"));
+
+                // The cell starts at the first column not covered by the previous cells
+                let mut column = self.rows[row]
+                    .last()
+                    .map_or(0, |cell| cell.column + cell.col_span);
+                while self.covered.contains(&(row, column)) {
+                    column += 1;
+                }
+                for r in row + 1..row + row_span {
+                    for c in column..column + col_span {
+                        self.covered.insert((r, c));
+                    }
+                }
+
+                self.cell = Some(TableCell {
+                    text: String::new(),
+                    row,
+                    column,
+                    row_span,
+                    col_span,
+                    is_header: name == "th" || self.in_thead,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn end(&mut self, name: &str) {
+        if self.nested > 0 {
+            if name == "table" {
+                self.nested -= 1;
+            }
+            return;
+        }
+
+        match name {
+            "thead" => self.in_thead = false,
+            "td" | "th" => {
+                if let Some(mut cell) = self.cell.take() {
+                    cell.text = cell.text.trim().to_string();
+                    self.rows[cell.row].push(cell);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Adds text to the cell being read, the whitespace between cells is ignored
+    pub(crate) fn text(&mut self, text: &str) {
+        if let Some(cell) = &mut self.cell {
+            cell.text.push_str(text);
+        }
+    }
+
+    /// Returns the rows without the empty ones, and the number of header rows
+    pub(crate) fn finish(self) -> (Vec<Vec<TableCell>>, usize) {
+        let mut rows: Vec<_> = self.rows.into_iter().filter(|r| !r.is_empty()).collect();
+        for (index, row) in rows.iter_mut().enumerate() {
+            for cell in row {
+                cell.row = index;
+            }
+        }
+        let header_rows = header_rows(&rows);
+        (rows, header_rows)
+    }
+}
+
+/// Detects the header rows. They are the leading rows of `th` or `thead` cells when the document
+/// has them. Otherwise, as in spreadsheets and csv, the first row is a header when all its cells
+/// are text and the rows below hold numbers
+fn header_rows(rows: &[Vec<TableCell>]) -> usize {
+    let explicit = rows
+        .iter()
+        .take_while(|row| row.iter().all(|cell| cell.is_header))
+        .count();
+    if explicit > 0 || rows.len() < 2 {
+        return explicit;
+    }
+
+    let is_number = |text: &str| {
+        let text = text.trim().trim_end_matches('%').replace(',', "");
+        !text.is_empty() && text.parse::<f64>().is_ok()
+    };
+    let first_row_is_text = rows[0]
+        .iter()
+        .all(|cell| !cell.text.is_empty() && !is_number(&cell.text));
+    let numbers_below = rows[1..].iter().flatten().any(|cell| is_number(&cell.text));
+
+    usize::from(first_row_is_text && numbers_below)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::xhtml::XhtmlReader;
+
+    fn table(xhtml: &str) -> (Vec<Vec<TableCell>>, usize) {
+        let mut reader = XhtmlReader::new(xhtml.as_bytes());
+        let mut builder = TableBuilder::default();
+        while let Some(event) = reader.next_event().unwrap() {
+            match event {
+                XhtmlEvent::Start { .. } => builder.start(&event),
+                XhtmlEvent::End { name } => builder.end(&name),
+                XhtmlEvent::Text(text) => builder.text(&text),
+            }
+        }
+        builder.finish()
+    }
+
+    #[test]
+    fn spans_test() {
+        let (rows, header_rows) = table(
+            r#"<html><body>
+<thead><tr><th colspan="2">Name</th><th>Total</th></tr></thead>
+<tbody><tr><td rowspan="2">A</td><td>x</td><td>1</td></tr>
+<tr><td>y</td><td>2</td></tr></tbody>
+</body></html>"#,
+        );
+
+        assert_eq!(header_rows, 1);
+        let positions: Vec<Vec<_>> = rows
+            .iter()
+            .map(|row| row.iter().map(|c| (c.text.as_str(), c.column)).collect())
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                vec![("Name", 0), ("Total", 2)],
+                vec![("A", 0), ("x", 1), ("1", 2)],
+                vec![("y", 1), ("2", 2)],
+            ]
+        );
+
+        let table = Table {
+            rows,
+            header_rows,
+            page_number: None,
+            page_title: None,
+            element_index: 0,
+        };
+        assert_eq!(table.num_columns(), 3);
+        assert_eq!(table.to_grid()[2], vec!["", "y", "2"]);
+    }
+
+    #[test]
+    fn detected_header_test() {
+        let (_rows, header_rows) = table(
+            "<html><body><tr><td>Year</td><td>Revenue</td></tr>\
+             <tr><td>2022</td><td>1,234.5</td></tr></body></html>",
+        );
+        assert_eq!(header_rows, 1);
+
+        let (_rows, header_rows) = table(
+            "<html><body><tr><td>a</td><td>b</td></tr><tr><td>c</td><td>d</td></tr></body></html>",
+        );
+        assert_eq!(header_rows, 0);
+    }
+}
@@ -80,6 +80,27 @@ pub fn jni_new_string_as_jvalue<'local>(
     Ok(JValueOwned::from(jstring))
 }
 
+/// creates a new java String[] from rust strings and returns it as a JValueOwned
+pub fn jni_new_string_array_as_jvalue<'local>(
+    env: &mut JNIEnv<'local>,
+    strings: &[String],
+) -> ExtractResult<JValueOwned<'local>> {
+    let array = env
+        .new_object_array(
+            strings.len() as sys::jsize,
+            "java/lang/String",
+            JObject::null(),
+        )
+        .map_err(|_e| Error::JniEnvCall("Couldn't create Java String array"))?;
+    for (i, s) in strings.iter().enumerate() {
+        let jstring = jni_new_string(env, s)?;
+        env.set_object_array_element(&array, i as sys::jsize, jstring)
+            .map_err(|_e| Error::JniEnvCall("Couldn't set Java String array element"))?;
+    }
+
+    Ok(JValueOwned::from(JObject::from(array)))
+}
+
 /// Converts a java object to a rust string
 pub fn jni_jobject_to_string<'local>(
     env: &mut JNIEnv<'local>,
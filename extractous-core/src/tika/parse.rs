@@ -1,13 +1,18 @@
 use std::sync::OnceLock;
 
-use crate::errors::ExtractResult;
+use crate::cancellation::ParseControl;
+use crate::errors::{Error, ExtractResult};
 use crate::tika::jni_utils::*;
+use crate::tika::reader_source::ReaderSource;
 use crate::tika::wrappers::*;
 use crate::{
-    CharSet, Metadata, OfficeParserConfig, PdfParserConfig, StreamReader, TesseractOcrConfig,
+    CharSet, DetectResult, DetectedLanguage, DetectionSource, ExtractOptions, ExtractedDocument,
+    Metadata, OfficeParserConfig, OutputFormat, PdfParserConfig, StreamReader, TesseractOcrConfig,
 };
-use jni::objects::JValue;
+use jni::objects::{JObject, JValue};
 use jni::{AttachGuard, JavaVM};
+use std::io::Read;
+use std::str::FromStr;
 
 /// Returns a reference to the shared VM isolate
 /// Instead of creating a new VM for every tika call, we create a single VM that is shared
@@ -25,53 +30,104 @@ fn get_vm_attach_current_thread<'local>() -> ExtractResult<AttachGuard<'local>>
     Ok(env)
 }
 
-fn parse_to_stream(
-    mut env: AttachGuard,
+/// Settings of one extraction, shared by all the parse calls. Built by the [`crate::Extractor`]
+/// from its configuration
+pub struct ParseSettings<'a> {
+    /// Encoding of the streams
+    pub char_set: CharSet,
+    /// Maximum length of the strings, -1 for no limit
+    pub max_length: i32,
+    pub pdf_conf: &'a PdfParserConfig,
+    pub office_conf: &'a OfficeParserConfig,
+    pub ocr_conf: &'a TesseractOcrConfig,
+    pub passwords: &'a [String],
+    pub tika_config: Option<&'a JTikaConfig>,
+    pub allowed_mime_types: &'a [String],
+    pub denied_mime_types: &'a [String],
+    pub output_format: OutputFormat,
+    /// Whether to detect the languages of the strings and of the recursive documents
+    pub detect_languages: bool,
+    pub control: &'a ParseControl,
+}
+
+/// JNI class of the file paths and urls given to the java parse calls
+const STRING_CLASS: &str = "java/lang/String";
+/// JNI class of the byte buffers given to the java parse calls
+const BYTE_BUFFER_CLASS: &str = "java/nio/ByteBuffer";
+/// JNI class of the readers given to the java parse calls
+const INPUT_STREAM_CLASS: &str = "java/io/InputStream";
+
+/// Returns the JNI signature of the `TikaNativeMain` parse methods, which all take the data
+/// source, the metadata with the hints and the `ai.yobix.ParseSettings`
+fn parse_signature(data_source_class: &str, result_class: &str) -> String {
+    format!(
+        "(L{data_source_class};\
+        Lorg/apache/tika/metadata/Metadata;\
+        Lai/yobix/ParseSettings;\
+        )L{result_class};"
+    )
+}
+
+/// Calls a parse method of `TikaNativeMain` with the given data source, the hints of `options`
+/// and the settings. Returns the result object and the monitor of the parsing, if any
+fn call_parse<'local>(
+    env: &mut AttachGuard<'local>,
     data_source_val: JValue,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    data_source_class: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
     method_name: &str,
-    signature: &str,
-) -> ExtractResult<(StreamReader, Metadata)> {
-    let charset_name_val = jni_new_string_as_jvalue(&mut env, &char_set.to_string())?;
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
+    result_class: &str,
+) -> ExtractResult<(JObject<'local>, Option<JParseMonitor>)> {
+    let j_metadata = JMetadata::new(env, options)?;
+    let j_settings = JParseSettings::new(env, settings)?;
 
-    // Make the java parse call
     let call_result = jni_call_static_method(
-        &mut env,
+        env,
         "ai/yobix/TikaNativeMain",
         method_name,
-        signature,
+        &parse_signature(data_source_class, result_class),
         &[
             data_source_val,
-            (&charset_name_val).into(),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
+            (&j_metadata.internal).into(),
+            (&j_settings.internal).into(),
         ],
     );
-    let call_result_obj = call_result?.l()?;
+    Ok((call_result?.l()?, j_settings.monitor))
+}
+
+fn parse_to_stream(
+    mut env: AttachGuard,
+    data_source_val: JValue,
+    data_source_class: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+    method_name: &str,
+) -> ExtractResult<(StreamReader, Metadata)> {
+    // Make the java parse call
+    let (call_result_obj, monitor) = call_parse(
+        &mut env,
+        data_source_val,
+        data_source_class,
+        options,
+        settings,
+        method_name,
+        "ai/yobix/ReaderResult",
+    )?;
 
     // Create and process the JReaderResult
     let result = JReaderResult::new(&mut env, call_result_obj)?;
-    let j_reader = JReaderInputStream::new(&mut env, result.java_reader)?;
+    // The reader keeps the monitor to tell timeouts and cancellations apart from other errors
+    let j_reader =
+        JReaderInputStream::new(&mut env, result.java_reader, result.parsing_reader, monitor)?;
 
-    Ok((StreamReader { inner: j_reader }, result.metadata))
+    Ok((StreamReader::new(j_reader), result.metadata))
 }
 
 pub fn parse_file(
     file_path: &str,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(StreamReader, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -79,84 +135,50 @@ pub fn parse_file(
     parse_to_stream(
         env,
         (&file_path_val).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        STRING_CLASS,
+        options,
+        settings,
         "parseFile",
-        "(Ljava/lang/String;\
-        Ljava/lang/String;\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/ReaderResult;",
     )
 }
 
-pub fn parse_bytes(
-    buffer: &[u8],
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+pub fn parse_url(
+    url: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(StreamReader, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
-    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
-    // jni_new_direct_buffer call, which requires a mutable pointer
-    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
-
-    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
-
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
     parse_to_stream(
         env,
-        (&byte_buffer).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
-        "parseBytes",
-        "(Ljava/nio/ByteBuffer;\
-        Ljava/lang/String;\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/ReaderResult;",
+        (&url_val).into(),
+        STRING_CLASS,
+        options,
+        settings,
+        "parseUrl",
     )
 }
 
-pub fn parse_url(
-    url: &str,
-    char_set: &CharSet,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+pub fn parse_reader(
+    reader: Box<dyn Read + Send + 'static>,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(StreamReader, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
-    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    // The java stream owns the source from now on and drops the reader once parsing is done,
+    // which happens on the background parsing thread
+    let source = ReaderSource::new(reader);
+    let input_stream = JRustInputStream::new(&mut env, &source)?;
+
     parse_to_stream(
         env,
-        (&url_val).into(),
-        char_set,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
-        "parseUrl",
-        "(Ljava/lang/String;\
-        Ljava/lang/String;\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/ReaderResult;",
+        (&input_stream.internal).into(),
+        INPUT_STREAM_CLASS,
+        options,
+        settings,
+        "parseInputStream",
     )
 }
 
@@ -164,33 +186,20 @@ pub fn parse_url(
 pub fn parse_to_string(
     mut env: AttachGuard,
     data_source_val: JValue,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    data_source_class: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
     method_name: &str,
-    signature: &str,
 ) -> ExtractResult<(String, Metadata)> {
-    let j_pdf_conf = JPDFParserConfig::new(&mut env, pdf_conf)?;
-    let j_office_conf = JOfficeParserConfig::new(&mut env, office_conf)?;
-    let j_ocr_conf = JTesseractOcrConfig::new(&mut env, ocr_conf)?;
-
-    let call_result = jni_call_static_method(
+    let (call_result_obj, _monitor) = call_parse(
         &mut env,
-        "ai/yobix/TikaNativeMain",
+        data_source_val,
+        data_source_class,
+        options,
+        settings,
         method_name,
-        signature,
-        &[
-            data_source_val,
-            JValue::Int(max_length),
-            (&j_pdf_conf.internal).into(),
-            (&j_office_conf.internal).into(),
-            (&j_ocr_conf.internal).into(),
-            JValue::Bool(if as_xml { 1 } else { 0 }),
-        ],
-    );
-    let call_result_obj = call_result?.l()?;
+        "ai/yobix/StringResult",
+    )?;
 
     // Create and process the JStringResult
     let result = JStringResult::new(&mut env, call_result_obj)?;
@@ -200,11 +209,8 @@ pub fn parse_to_string(
 /// Parses a file to a string using the Apache Tika library.
 pub fn parse_file_to_string(
     file_path: &str,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -212,30 +218,18 @@ pub fn parse_file_to_string(
     parse_to_string(
         env,
         (&file_path_val).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        STRING_CLASS,
+        options,
+        settings,
         "parseFileToString",
-        "(Ljava/lang/String;\
-        I\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/StringResult;",
     )
 }
 
 /// Parses bytes to a string using the Apache Tika library.
 pub fn parse_bytes_to_string(
     buffer: &[u8],
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -248,30 +242,48 @@ pub fn parse_bytes_to_string(
     parse_to_string(
         env,
         (&byte_buffer).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        BYTE_BUFFER_CLASS,
+        options,
+        settings,
         "parseBytesToString",
-        "(Ljava/nio/ByteBuffer;\
-        I\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/StringResult;",
     )
 }
 
+/// Parses a reader to a string using the Apache Tika library.
+pub fn parse_reader_to_string(
+    reader: Box<dyn Read + Send + '_>,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+) -> ExtractResult<(String, Metadata)> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // SAFETY: java reads from the source only while holding its lock, and the reader is dropped
+    // under that lock by the close guard below, before the borrow it might hold ends, also when
+    // unwinding or when a read panicked. Afterwards the source only yields the end of stream,
+    // even to a parsing thread abandoned on timeout.
+    let reader = unsafe {
+        std::mem::transmute::<Box<dyn Read + Send + '_>, Box<dyn Read + Send + 'static>>(reader)
+    };
+    let source = ReaderSource::new(reader);
+    let _close_guard = source.close_on_drop();
+
+    JRustInputStream::new(&mut env, &source).and_then(|input_stream| {
+        parse_to_string(
+            env,
+            (&input_stream.internal).into(),
+            INPUT_STREAM_CLASS,
+            options,
+            settings,
+            "parseInputStreamToString",
+        )
+    })
+}
+
 /// Parses a url to a string using the Apache Tika library.
 pub fn parse_url_to_string(
     url: &str,
-    max_length: i32,
-    pdf_conf: &PdfParserConfig,
-    office_conf: &OfficeParserConfig,
-    ocr_conf: &TesseractOcrConfig,
-    as_xml: bool,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
 ) -> ExtractResult<(String, Metadata)> {
     let mut env = get_vm_attach_current_thread()?;
 
@@ -279,18 +291,270 @@ pub fn parse_url_to_string(
     parse_to_string(
         env,
         (&url_val).into(),
-        max_length,
-        pdf_conf,
-        office_conf,
-        ocr_conf,
-        as_xml,
+        STRING_CLASS,
+        options,
+        settings,
         "parseUrlToString",
-        "(Ljava/lang/String;\
-        I\
-        Lorg/apache/tika/parser/pdf/PDFParserConfig;\
-        Lorg/apache/tika/parser/microsoft/OfficeParserConfig;\
-        Lorg/apache/tika/parser/ocr/TesseractOCRConfig;\
-        Z\
-        )Lai/yobix/StringResult;",
     )
 }
+
+/// Tika metadata key holding the content of a document parsed recursively
+const TIKA_CONTENT_KEY: &str = "X-TIKA:content";
+/// Tika metadata key holding the path of an embedded document inside its container
+const TIKA_EMBEDDED_RESOURCE_PATH_KEY: &str = "X-TIKA:embedded_resource_path";
+/// Tika metadata key holding the nesting depth of an embedded document
+const TIKA_EMBEDDED_DEPTH_KEY: &str = "X-TIKA:embedded_depth";
+
+/// Parses a data source and its embedded documents recursively using the Apache Tika library.
+pub fn parse_recursive(
+    mut env: AttachGuard,
+    data_source_val: JValue,
+    data_source_class: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+    method_name: &str,
+) -> ExtractResult<Vec<ExtractedDocument>> {
+    let (call_result_obj, _monitor) = call_parse(
+        &mut env,
+        data_source_val,
+        data_source_class,
+        options,
+        settings,
+        method_name,
+        "ai/yobix/RecursiveResult",
+    )?;
+
+    // Create and process the JRecursiveResult
+    let result = JRecursiveResult::new(&mut env, call_result_obj)?;
+    let documents = result
+        .metadata_list
+        .into_iter()
+        .map(|mut metadata| {
+            let content = metadata
+                .remove(TIKA_CONTENT_KEY)
+                .map(|values| values.concat())
+                .unwrap_or_default();
+            let embedded_path = metadata
+                .get(TIKA_EMBEDDED_RESOURCE_PATH_KEY)
+                .and_then(|values| values.first().cloned());
+            let depth = metadata
+                .get(TIKA_EMBEDDED_DEPTH_KEY)
+                .and_then(|values| values.first())
+                .and_then(|depth| depth.parse().ok())
+                .unwrap_or(0);
+
+            ExtractedDocument {
+                content,
+                metadata,
+                embedded_path,
+                depth,
+            }
+        })
+        .collect();
+
+    Ok(documents)
+}
+
+/// Parses a file and its embedded documents recursively using the Apache Tika library.
+pub fn parse_file_recursive(
+    file_path: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+) -> ExtractResult<Vec<ExtractedDocument>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    parse_recursive(
+        env,
+        (&file_path_val).into(),
+        STRING_CLASS,
+        options,
+        settings,
+        "parseFileRecursive",
+    )
+}
+
+/// Parses bytes and their embedded documents recursively using the Apache Tika library.
+pub fn parse_bytes_recursive(
+    buffer: &[u8],
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+) -> ExtractResult<Vec<ExtractedDocument>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    parse_recursive(
+        env,
+        (&byte_buffer).into(),
+        BYTE_BUFFER_CLASS,
+        options,
+        settings,
+        "parseBytesRecursive",
+    )
+}
+
+/// Parses a url and its embedded documents recursively using the Apache Tika library.
+pub fn parse_url_recursive(
+    url: &str,
+    options: &ExtractOptions,
+    settings: &ParseSettings,
+) -> ExtractResult<Vec<ExtractedDocument>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    parse_recursive(
+        env,
+        (&url_val).into(),
+        STRING_CLASS,
+        options,
+        settings,
+        "parseUrlRecursive",
+    )
+}
+
+/// Detects the mime type of a data source without parsing it, with the hints of `options`
+fn detect(
+    mut env: AttachGuard,
+    data_source_val: JValue,
+    data_source_class: &str,
+    options: &ExtractOptions,
+    tika_config: Option<&JTikaConfig>,
+    method_name: &str,
+) -> ExtractResult<DetectResult> {
+    let j_metadata = JMetadata::new(&mut env, options)?;
+    let null_config = JObject::null();
+    let tika_config_obj = tika_config.map_or(&null_config, |c| c.internal.as_obj());
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        method_name,
+        &format!(
+            "(L{data_source_class};\
+            Lorg/apache/tika/metadata/Metadata;\
+            Lorg/apache/tika/config/TikaConfig;\
+            )Lai/yobix/DetectResult;"
+        ),
+        &[
+            data_source_val,
+            (&j_metadata.internal).into(),
+            JValue::Object(tika_config_obj),
+        ],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // Create and process the JDetectResult
+    let result = JDetectResult::new(&mut env, call_result_obj)?;
+    let source = DetectionSource::from_str(&result.source)
+        .map_err(|_e| Error::Unknown(format!("Unknown detection source: {}", result.source)))?;
+
+    Ok(DetectResult {
+        mime_type: result.mime_type,
+        source,
+        metadata: result.metadata,
+    })
+}
+
+/// Detects the mime type of a file using the Apache Tika library.
+pub fn detect_file(
+    file_path: &str,
+    options: &ExtractOptions,
+    tika_config: Option<&JTikaConfig>,
+) -> ExtractResult<DetectResult> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let file_path_val = jni_new_string_as_jvalue(&mut env, file_path)?;
+    detect(
+        env,
+        (&file_path_val).into(),
+        STRING_CLASS,
+        options,
+        tika_config,
+        "detectFile",
+    )
+}
+
+/// Detects the mime type of bytes using the Apache Tika library.
+pub fn detect_bytes(
+    buffer: &[u8],
+    options: &ExtractOptions,
+    tika_config: Option<&JTikaConfig>,
+) -> ExtractResult<DetectResult> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    // Because we know the buffer is used for reading only, cast it to *mut u8 to satisfy the
+    // jni_new_direct_buffer call, which requires a mutable pointer
+    let mut_ptr: *mut u8 = buffer.as_ptr() as *mut u8;
+
+    let byte_buffer = jni_new_direct_buffer(&mut env, mut_ptr, buffer.len())?;
+
+    detect(
+        env,
+        (&byte_buffer).into(),
+        BYTE_BUFFER_CLASS,
+        options,
+        tika_config,
+        "detectBytes",
+    )
+}
+
+/// Detects the mime type of a url using the Apache Tika library.
+pub fn detect_url(
+    url: &str,
+    options: &ExtractOptions,
+    tika_config: Option<&JTikaConfig>,
+) -> ExtractResult<DetectResult> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let url_val = jni_new_string_as_jvalue(&mut env, url)?;
+    detect(
+        env,
+        (&url_val).into(),
+        STRING_CLASS,
+        options,
+        tika_config,
+        "detectUrl",
+    )
+}
+
+/// Loads a tika config from the content of a tika-config.xml, to be given to the detect and parse
+/// calls
+pub fn load_tika_config(xml: &str) -> ExtractResult<JTikaConfig> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let xml_val = jni_new_string_as_jvalue(&mut env, xml)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "loadTikaConfig",
+        "(Ljava/lang/String;)Lai/yobix/TikaConfigResult;",
+        &[(&xml_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    JTikaConfig::new(&mut env, call_result_obj)
+}
+
+/// Detects the languages of a text using the Apache Tika library. Returns the languages found,
+/// most probable first
+pub fn detect_languages(text: &str) -> ExtractResult<Vec<DetectedLanguage>> {
+    let mut env = get_vm_attach_current_thread()?;
+
+    let text_val = jni_new_string_as_jvalue(&mut env, text)?;
+    let call_result = jni_call_static_method(
+        &mut env,
+        "ai/yobix/TikaNativeMain",
+        "detectLanguages",
+        "(Ljava/lang/String;)Lai/yobix/LanguageDetectionResult;",
+        &[(&text_val).into()],
+    );
+    let call_result_obj = call_result?.l()?;
+
+    // Create and process the JLanguageDetectionResult
+    let result = JLanguageDetectionResult::new(&mut env, call_result_obj)?;
+    Ok(result.languages)
+}
@@ -0,0 +1,222 @@
+use std::io::Read;
+use std::os::raw::c_void;
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::errors::{Error, ExtractResult};
+use crate::DEFAULT_BUF_SIZE;
+use bytemuck::cast_slice;
+use jni::objects::{JByteArray, JClass};
+use jni::sys::{jint, jlong};
+use jni::{JNIEnv, NativeMethod};
+
+/// Rust reader shared with a java `ai.yobix.RustInputStream`, which pulls bytes from it in chunks.
+///
+/// The java stream holds a strong reference to the source through its `handle`. The reference
+/// is released when the java stream is closed, which the parsers always do once they are done.
+pub(crate) struct ReaderSource {
+    state: Mutex<ReaderState>,
+}
+
+struct ReaderState {
+    reader: Option<Box<dyn Read + Send + 'static>>,
+    chunk: Vec<u8>,
+}
+
+impl ReaderSource {
+    pub(crate) fn new(reader: Box<dyn Read + Send + 'static>) -> Arc<Self> {
+        Arc::new(Self {
+            state: Mutex::new(ReaderState {
+                reader: Some(reader),
+                chunk: Vec::with_capacity(DEFAULT_BUF_SIZE),
+            }),
+        })
+    }
+
+    /// Drops the underlying reader. Any later read from java sees the end of the stream.
+    /// The reader is dropped even if a panicking read poisoned the lock
+    pub(crate) fn close(&self) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.reader = None;
+    }
+
+    /// Returns a guard that closes the source when dropped, including while unwinding
+    pub(crate) fn close_on_drop(&self) -> CloseOnDrop<'_> {
+        CloseOnDrop(self)
+    }
+
+    /// Gives java its own strong reference to the source as an opaque handle
+    pub(crate) fn into_handle(source: &Arc<Self>) -> jlong {
+        Arc::into_raw(Arc::clone(source)) as jlong
+    }
+
+    /// Releases the strong reference owned by a handle.
+    ///
+    /// # Safety
+    /// The handle must come from [`ReaderSource::into_handle`] and must be released only once
+    pub(crate) unsafe fn release_handle(handle: jlong) {
+        drop(Arc::from_raw(handle as *const Self));
+    }
+
+    /// Reads at most `len` bytes from the reader and copies them into the java array.
+    /// Returns the number of bytes read or -1 when the end of the stream is reached
+    fn read_into(
+        &self,
+        env: &mut JNIEnv,
+        buf: &JByteArray,
+        off: jint,
+        len: jint,
+    ) -> ExtractResult<jint> {
+        let mut state = self
+            .state
+            .lock()
+            .map_err(|_e| Error::IoError("Rust reader lock is poisoned".to_string()))?;
+        let ReaderState { reader, chunk } = &mut *state;
+
+        let Some(reader) = reader else {
+            return Ok(-1);
+        };
+
+        chunk.resize(len as usize, 0);
+        let num_read_bytes = loop {
+            match reader.read(chunk) {
+                Ok(n) => break n,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::IoError(e.to_string())),
+            }
+        };
+
+        if num_read_bytes == 0 {
+            return Ok(-1);
+        }
+
+        env.set_byte_array_region(buf, off, cast_slice(&chunk[..num_read_bytes]))
+            .map_err(|_e| Error::JniEnvCall("Failed to set byte array region"))?;
+        Ok(num_read_bytes as jint)
+    }
+}
+
+/// Closes a [`ReaderSource`] when dropped, see [`ReaderSource::close_on_drop`]
+pub(crate) struct CloseOnDrop<'a>(&'a ReaderSource);
+
+impl Drop for CloseOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+/// Implementation of `ai.yobix.RustInputStream.nativeRead`
+extern "system" fn native_read<'local>(
+    mut env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+    buf: JByteArray<'local>,
+    off: jint,
+    len: jint,
+) -> jint {
+    // The java stream keeps its strong reference alive until nativeClose is called
+    let source = unsafe { &*(handle as *const ReaderSource) };
+
+    // Panics must never unwind into the java frames
+    let result = std::panic::catch_unwind(AssertUnwindSafe(|| {
+        source.read_into(&mut env, &buf, off, len)
+    }));
+    let message = match result {
+        Ok(Ok(num_read_bytes)) => return num_read_bytes,
+        Ok(Err(e)) => e.to_string(),
+        Err(_) => "Rust reader panicked".to_string(),
+    };
+
+    env.throw_new("java/io/IOException", message).ok();
+    -1
+}
+
+/// Implementation of `ai.yobix.RustInputStream.nativeClose`
+extern "system" fn native_close<'local>(
+    _env: JNIEnv<'local>,
+    _class: JClass<'local>,
+    handle: jlong,
+) {
+    // The java stream calls this only once, see RustInputStream.close
+    let _ = std::panic::catch_unwind(|| unsafe { ReaderSource::release_handle(handle) });
+}
+
+/// Registers the native methods of `ai.yobix.RustInputStream`. Registration is done only once
+/// per isolate, before the first java stream is created.
+pub(crate) fn register_reader_natives(env: &mut JNIEnv) -> ExtractResult<()> {
+    static REGISTERED: AtomicBool = AtomicBool::new(false);
+    if REGISTERED.load(Ordering::Acquire) {
+        return Ok(());
+    }
+
+    // Registering the same methods twice is harmless, so no need to lock here
+    let class = env.find_class("ai/yobix/RustInputStream")?;
+    env.register_native_methods(
+        &class,
+        &[
+            NativeMethod {
+                name: "nativeRead".into(),
+                sig: "(J[BII)I".into(),
+                fn_ptr: native_read as *mut c_void,
+            },
+            NativeMethod {
+                name: "nativeClose".into(),
+                sig: "(J)V".into(),
+                fn_ptr: native_close as *mut c_void,
+            },
+        ],
+    )?;
+
+    REGISTERED.store(true, Ordering::Release);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reader recording when it is dropped
+    struct DropFlagReader(Arc<AtomicBool>);
+
+    impl Read for DropFlagReader {
+        fn read(&mut self, _buf: &mut [u8]) -> std::io::Result<usize> {
+            Ok(0)
+        }
+    }
+
+    impl Drop for DropFlagReader {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    fn source_with_flag() -> (Arc<ReaderSource>, Arc<AtomicBool>) {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let source = ReaderSource::new(Box::new(DropFlagReader(Arc::clone(&dropped))));
+        (source, dropped)
+    }
+
+    #[test]
+    fn close_poisoned_source_test() {
+        let (source, dropped) = source_with_flag();
+        let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let _state = source.state.lock().unwrap();
+            panic!("read panicked");
+        }));
+        assert!(source.state.is_poisoned());
+
+        source.close();
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn close_on_drop_while_unwinding_test() {
+        let (source, dropped) = source_with_flag();
+        let _ = std::panic::catch_unwind(AssertUnwindSafe(|| {
+            let _close_guard = source.close_on_drop();
+            panic!("parsing panicked");
+        }));
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
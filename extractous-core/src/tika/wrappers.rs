@@ -1,14 +1,20 @@
-use crate::errors::{Error, ExtractResult};
+use crate::cancellation::{ParseControl, Registration};
+use crate::errors::{Error, ErrorContext, ExtractResult};
 use crate::tika::jni_utils::{
-    jni_call_method, jni_jobject_to_string, jni_new_string_as_jvalue,
-    jni_tika_metadata_to_rust_metadata,
+    jni_call_method, jni_jobject_array_to_vec, jni_jobject_to_string,
+    jni_new_string_array_as_jvalue, jni_new_string_as_jvalue, jni_tika_metadata_to_rust_metadata,
+};
+use crate::tika::reader_source::{register_reader_natives, ReaderSource};
+use crate::tika::{vm, ParseSettings};
+use crate::{
+    DetectedLanguage, ExtractOptions, Metadata, OfficeParserConfig, PdfParserConfig,
+    TesseractOcrConfig, DEFAULT_BUF_SIZE,
 };
-use crate::tika::vm;
-use crate::{Metadata, OfficeParserConfig, PdfParserConfig, TesseractOcrConfig, DEFAULT_BUF_SIZE};
 use bytemuck::cast_slice_mut;
-use jni::objects::{GlobalRef, JByteArray, JObject, JValue};
+use jni::objects::{GlobalRef, JByteArray, JFloatArray, JObject, JObjectArray, JValue};
 use jni::sys::jsize;
 use jni::JNIEnv;
+use std::sync::Arc;
 
 /// Wrapper for [`JObject`]s that contain `org.apache.commons.io.input.ReaderInputStream`
 /// It saves a GlobalRef to the java object, which is cleared when the last GlobalRef is dropped
@@ -18,12 +24,19 @@ pub struct JReaderInputStream {
     internal: GlobalRef,
     buffer: GlobalRef,
     capacity: jsize,
+    monitor: Option<Arc<JParseMonitor>>,
+    /// The `ai.yobix.ParsingReader` the stream reads from
+    parsing_reader: GlobalRef,
+    /// The metadata once the parsing is done, set when the end of the stream is reached
+    final_metadata: Option<Metadata>,
 }
 
 impl JReaderInputStream {
     pub(crate) fn new<'local>(
         env: &mut JNIEnv<'local>,
         obj: JObject<'local>,
+        parsing_reader: JObject<'local>,
+        monitor: Option<JParseMonitor>,
     ) -> ExtractResult<Self> {
         // Creates new jbyte array
         let capacity = DEFAULT_BUF_SIZE as jsize;
@@ -33,9 +46,32 @@ impl JReaderInputStream {
             internal: env.new_global_ref(obj)?,
             buffer: env.new_global_ref(jbyte_array)?,
             capacity,
+            monitor: monitor.map(Arc::new),
+            parsing_reader: env.new_global_ref(parsing_reader)?,
+            final_metadata: None,
         })
     }
 
+    /// Returns the metadata once the parsing is done, `None` until the end of the stream
+    pub(crate) fn final_metadata(&self) -> Option<&Metadata> {
+        self.final_metadata.as_ref()
+    }
+
+    /// Waits for the parsing thread, which ends right after the output, and converts the
+    /// metadata it filled in
+    fn load_final_metadata(&mut self, env: &mut JNIEnv) -> ExtractResult<()> {
+        let tika_metadata_obj = jni_call_method(
+            env,
+            &self.parsing_reader,
+            "getFinalMetadata",
+            "()Lorg/apache/tika/metadata/Metadata;",
+            &[],
+        )?
+        .l()?;
+        self.final_metadata = Some(jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?);
+        Ok(())
+    }
+
     pub(crate) fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         let mut env = vm().attach_current_thread().map_err(Error::JniError)?;
 
@@ -72,7 +108,10 @@ impl JReaderInputStream {
                 JValue::Int(length),
             ],
         );
-        let num_read_bytes = call_result?.i().map_err(Error::JniError)?;
+        let num_read_bytes = match call_result {
+            Ok(value) => value.i().map_err(Error::JniError)?,
+            Err(e) => return Err(self.read_error(&mut env, e).into()),
+        };
 
         // Get self.buffer object as a local reference
         let obj_local = env
@@ -89,11 +128,60 @@ impl JReaderInputStream {
 
         if num_read_bytes == -1 {
             // End of stream reached
+            if self.final_metadata.is_none() {
+                self.load_final_metadata(&mut env)?;
+            }
             Ok(0)
         } else {
             Ok(num_read_bytes as usize)
         }
     }
+
+    /// Replaces the error of a failed read with its cause: the timeout or cancellation, or else
+    /// the exception thrown by the parsing running in the background
+    fn read_error(&self, env: &mut JNIEnv, error: Error) -> Error {
+        if self.monitor.is_some() {
+            if let Ok(Some(abort_error)) = self.abort_error(env) {
+                return abort_error;
+            }
+        }
+        match self.parse_error(env) {
+            Ok(Some(parse_error)) => parse_error,
+            _ => error,
+        }
+    }
+
+    /// Returns the timeout or cancellation error if the monitor aborted the parsing
+    fn abort_error(&self, env: &mut JNIEnv) -> ExtractResult<Option<Error>> {
+        let error_obj = jni_call_method(
+            env,
+            &self.parsing_reader,
+            "getAbortError",
+            "()Lai/yobix/ErrorInfo;",
+            &[],
+        )?
+        .l()?;
+        if error_obj.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(error_info_to_error(env, error_obj)?))
+    }
+
+    /// Returns the error of the exception thrown by the parsing
+    fn parse_error(&self, env: &mut JNIEnv) -> ExtractResult<Option<Error>> {
+        let error_obj = jni_call_method(
+            env,
+            &self.parsing_reader,
+            "getError",
+            "()Lai/yobix/ErrorInfo;",
+            &[],
+        )?
+        .l()?;
+        if error_obj.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(error_info_to_error(env, error_obj)?))
+    }
 }
 
 impl Drop for JReaderInputStream {
@@ -110,6 +198,65 @@ impl Drop for JReaderInputStream {
     }
 }
 
+/// Converts the status and error message of a java result object to an [`Error`]
+fn status_error(status: i8, msg: String) -> Error {
+    match status {
+        1 => Error::IoError(msg),
+        2 => Error::ParseError(msg),
+        6 => Error::UnsupportedEncoding(msg),
+        _ => Error::Unknown(msg),
+    }
+}
+
+/// Converts an `ai.yobix.ErrorInfo` to an [`Error`], keeping the path, the mime type and the java
+/// exception of the error for the variants that have an [`ErrorContext`]
+fn error_info_to_error<'local>(
+    env: &mut JNIEnv<'local>,
+    obj: JObject<'local>,
+) -> ExtractResult<Error> {
+    let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
+    let message = jni_nullable_string(env, &obj, "getMessage")?
+        .unwrap_or_else(|| "Unknown error".to_string());
+
+    let context = ErrorContext {
+        path: jni_nullable_string(env, &obj, "getPath")?,
+        mime_type: jni_nullable_string(env, &obj, "getMimeType")?,
+        java_class: jni_nullable_string(env, &obj, "getExceptionClass")?,
+        stack_trace: jni_nullable_string(env, &obj, "getStackTrace")?,
+        message,
+    };
+
+    Ok(match status {
+        3 | 15 => Error::NetworkError(context),
+        4 => Error::Timeout(context),
+        5 => Error::Cancelled(context),
+        7 => Error::FileNotFound(context),
+        8 => Error::PermissionDenied(context),
+        9 => Error::UnsupportedFormat(context),
+        10 => Error::EncryptedDocument(context),
+        11 => Error::CorruptDocument(context),
+        12 => Error::ZeroByteFile(context),
+        13 => Error::WriteLimitReached(context),
+        14 => Error::OcrFailure(context),
+        16 => Error::InvalidConfig(context.message),
+        17 => Error::Rejected(context),
+        _ => status_error(status, context.message),
+    })
+}
+
+/// Calls a java getter returning a String that can be null
+fn jni_nullable_string<'local>(
+    env: &mut JNIEnv<'local>,
+    obj: &JObject<'local>,
+    method: &str,
+) -> ExtractResult<Option<String>> {
+    let str_obj = jni_call_method(env, obj, method, "()Ljava/lang/String;", &[])?.l()?;
+    if str_obj.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(jni_jobject_to_string(env, str_obj)?))
+}
+
 /// Wrapper for the Java class  `ai.yobix.StringResult`
 /// Upon creation it parses the java StringResult object and saves the converted Rust string
 pub struct JStringResult {
@@ -122,16 +269,9 @@ impl<'local> JStringResult {
         let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
 
         if is_error {
-            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
-            let msg_obj = env
-                .call_method(&obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
-                .l()?;
-            let msg = jni_jobject_to_string(env, msg_obj)?;
-            match status {
-                1 => Err(Error::IoError(msg)),
-                2 => Err(Error::ParseError(msg)),
-                _ => Err(Error::Unknown(msg)),
-            }
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
         } else {
             let call_result_obj = env
                 .call_method(&obj, "getContent", "()Ljava/lang/String;", &[])?
@@ -153,9 +293,11 @@ impl<'local> JStringResult {
 
 /// Wrapper for the Java class  `ai.yobix.ReaderResult`
 /// Upon creation it parses the java ReaderResult object and saves the java
-/// `org.apache.commons.io.input.ReaderInputStream` object, which later can be used for reading
+/// `org.apache.commons.io.input.ReaderInputStream` object, which later can be used for reading,
+/// and the `ai.yobix.ParsingReader` object that gives the final metadata
 pub struct JReaderResult<'local> {
     pub java_reader: JObject<'local>,
+    pub parsing_reader: JObject<'local>,
     pub metadata: Metadata,
 }
 
@@ -164,16 +306,9 @@ impl<'local> JReaderResult<'local> {
         let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
 
         if is_error {
-            let status = jni_call_method(env, &obj, "getStatus", "()B", &[])?.b()?;
-            let msg_obj = env
-                .call_method(&obj, "getErrorMessage", "()Ljava/lang/String;", &[])?
-                .l()?;
-            let msg = jni_jobject_to_string(env, msg_obj)?;
-            match status {
-                1 => Err(Error::IoError(msg)),
-                2 => Err(Error::ParseError(msg)),
-                _ => Err(Error::Unknown(msg)),
-            }
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
         } else {
             let reader_obj = jni_call_method(
                 env,
@@ -183,6 +318,14 @@ impl<'local> JReaderResult<'local> {
                 &[],
             )?
             .l()?;
+            let parsing_reader_obj = jni_call_method(
+                env,
+                &obj,
+                "getParsingReader",
+                "()Lai/yobix/ParsingReader;",
+                &[],
+            )?
+            .l()?;
 
             let tika_metadata_obj: JObject = env
                 .call_method(
@@ -196,12 +339,233 @@ impl<'local> JReaderResult<'local> {
 
             Ok(Self {
                 java_reader: reader_obj,
+                parsing_reader: parsing_reader_obj,
                 metadata,
             })
         }
     }
 }
 
+/// Wrapper for the Java class  `ai.yobix.DetectResult`
+/// Upon creation it parses the java DetectResult object and saves the detected mime type, the
+/// name of the detection source and the metadata collected while detecting
+pub struct JDetectResult {
+    pub mime_type: String,
+    pub source: String,
+    pub metadata: Metadata,
+}
+
+impl<'local> JDetectResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
+        } else {
+            let mime_type_obj =
+                jni_call_method(env, &obj, "getMimeType", "()Ljava/lang/String;", &[])?.l()?;
+            let mime_type = jni_jobject_to_string(env, mime_type_obj)?;
+            let source_obj =
+                jni_call_method(env, &obj, "getSource", "()Ljava/lang/String;", &[])?.l()?;
+            let source = jni_jobject_to_string(env, source_obj)?;
+            let tika_metadata_obj: JObject = env
+                .call_method(
+                    &obj,
+                    "getMetadata",
+                    "()Lorg/apache/tika/metadata/Metadata;",
+                    &[],
+                )?
+                .l()?;
+            let metadata = jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?;
+
+            Ok(Self {
+                mime_type,
+                source,
+                metadata,
+            })
+        }
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.LanguageDetectionResult`
+/// Upon creation it parses the java LanguageDetectionResult object and saves the detected
+/// languages, most probable first
+pub struct JLanguageDetectionResult {
+    pub languages: Vec<DetectedLanguage>,
+}
+
+impl<'local> JLanguageDetectionResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
+        } else {
+            let languages_obj =
+                jni_call_method(env, &obj, "getLanguages", "()[Ljava/lang/String;", &[])?.l()?;
+            let languages = jni_jobject_array_to_vec(env, languages_obj)?;
+
+            let confidences_obj = jni_call_method(env, &obj, "getConfidences", "()[F", &[])?.l()?;
+            let confidences_array = JFloatArray::from(confidences_obj);
+            let mut confidences = vec![0.0; env.get_array_length(&confidences_array)? as usize];
+            env.get_float_array_region(&confidences_array, 0, &mut confidences)?;
+
+            let languages = languages
+                .into_iter()
+                .zip(confidences)
+                .map(|(language, confidence)| DetectedLanguage {
+                    language,
+                    confidence,
+                })
+                .collect();
+
+            Ok(Self { languages })
+        }
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.TikaConfigResult`
+/// Upon creation it parses the java TikaConfigResult object and saves a GlobalRef to the loaded
+/// `org.apache.tika.config.TikaConfig`, which is shared by the clones of the extractor
+#[derive(Debug, Clone)]
+pub struct JTikaConfig {
+    pub(crate) internal: GlobalRef,
+}
+
+impl<'local> JTikaConfig {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
+        } else {
+            let config_obj = jni_call_method(
+                env,
+                &obj,
+                "getConfig",
+                "()Lorg/apache/tika/config/TikaConfig;",
+                &[],
+            )?
+            .l()?;
+            let internal = env.new_global_ref(config_obj)?;
+            Ok(Self { internal })
+        }
+    }
+}
+
+/// Wrapper for the Java class  `ai.yobix.RecursiveResult`
+/// Upon creation it parses the java RecursiveResult object and converts the metadata of every
+/// parsed document. The content of each document is still stored in its metadata
+pub struct JRecursiveResult {
+    pub metadata_list: Vec<Metadata>,
+}
+
+impl<'local> JRecursiveResult {
+    pub(crate) fn new(env: &mut JNIEnv<'local>, obj: JObject<'local>) -> ExtractResult<Self> {
+        let is_error = jni_call_method(env, &obj, "isError", "()Z", &[])?.z()?;
+
+        if is_error {
+            let error_obj =
+                jni_call_method(env, &obj, "getError", "()Lai/yobix/ErrorInfo;", &[])?.l()?;
+            Err(error_info_to_error(env, error_obj)?)
+        } else {
+            let j_metadata_array = jni_call_method(
+                env,
+                &obj,
+                "getMetadataList",
+                "()[Lorg/apache/tika/metadata/Metadata;",
+                &[],
+            )?
+            .l()?;
+            let j_metadata_array = JObjectArray::from(j_metadata_array);
+            let length = env.get_array_length(&j_metadata_array)?;
+
+            let mut metadata_list = Vec::with_capacity(length as usize);
+            for i in 0..length {
+                let tika_metadata_obj = env.get_object_array_element(&j_metadata_array, i)?;
+                let metadata = jni_tika_metadata_to_rust_metadata(env, tika_metadata_obj)?;
+                metadata_list.push(metadata);
+            }
+
+            Ok(Self { metadata_list })
+        }
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.RustInputStream`.
+/// The java stream pulls its bytes from a [`ReaderSource`] and owns a strong reference to it
+pub(crate) struct JRustInputStream<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JRustInputStream<'local> {
+    /// Creates a new object instance of `ai.yobix.RustInputStream` in the java world that reads
+    /// from the given source
+    pub(crate) fn new(env: &mut JNIEnv<'local>, source: &Arc<ReaderSource>) -> ExtractResult<Self> {
+        register_reader_natives(env)?;
+
+        let class = env.find_class("ai/yobix/RustInputStream")?;
+        let handle = ReaderSource::into_handle(source);
+        match env.new_object(&class, "(J)V", &[JValue::Long(handle)]) {
+            Ok(obj) => Ok(Self { internal: obj }),
+            Err(e) => {
+                // The java stream was not created, so nobody else will release the handle
+                unsafe { ReaderSource::release_handle(handle) };
+                Err(Error::JniError(e))
+            }
+        }
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ParseMonitor`, which enforces the timeout and
+/// the cancellation of one extraction. Keeps the monitor registered with the cancellation token
+/// of the extraction until dropped
+pub(crate) struct JParseMonitor {
+    pub(crate) internal: GlobalRef,
+    _registration: Option<Registration>,
+}
+
+impl JParseMonitor {
+    /// Creates a new object instance of `ai.yobix.ParseMonitor` in the java world. Returns None
+    /// if the extraction is neither time limited nor cancellable
+    pub(crate) fn new(env: &mut JNIEnv, control: &ParseControl) -> ExtractResult<Option<Self>> {
+        if control.is_unbounded() {
+            return Ok(None);
+        }
+
+        // 0 means no timeout on the java side, so round sub-millisecond timeouts up
+        let timeout_millis = control
+            .timeout
+            .map(|timeout| timeout.as_millis().clamp(1, i64::MAX as u128) as i64)
+            .unwrap_or(0);
+        let class = env.find_class("ai/yobix/ParseMonitor")?;
+        let obj = env.new_object(&class, "(J)V", &[JValue::Long(timeout_millis)])?;
+        let internal = env.new_global_ref(obj)?;
+
+        let registration = control
+            .cancellation_token
+            .as_ref()
+            .map(|token| token.register(&internal));
+        Ok(Some(Self {
+            internal,
+            _registration: registration,
+        }))
+    }
+}
+
+/// Aborts the extraction watched by the given `ai.yobix.ParseMonitor`
+pub(crate) fn cancel_parse_monitor(monitor: &GlobalRef) {
+    if let Ok(mut env) = vm().attach_current_thread() {
+        jni_call_method(&mut env, monitor, "cancel", "()V", &[]).ok();
+    }
+}
+
 /// Wrapper for [`JObject`]s that contain `org.apache.tika.parser.pdf.PDFParserConfig`.
 /// Looks up the class and method IDs on creation rather than for every method call.
 pub(crate) struct JPDFParserConfig<'local> {
@@ -416,3 +780,167 @@ impl<'local> JTesseractOcrConfig<'local> {
         Ok(Self { internal: obj })
     }
 }
+
+/// Wrapper for [`JObject`]s that contain `ai.yobix.ParseSettings`, the settings of one extraction
+/// given to all the parse calls. Keeps the monitor of the extraction, if any, which the streams
+/// take over
+pub(crate) struct JParseSettings<'local> {
+    pub(crate) internal: JObject<'local>,
+    pub(crate) monitor: Option<JParseMonitor>,
+}
+
+impl<'local> JParseSettings<'local> {
+    /// Creates a new object instance of `ai.yobix.ParseSettings` in the java world
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, settings: &ParseSettings) -> ExtractResult<Self> {
+        // Create the java object
+        let class = env.find_class("ai/yobix/ParseSettings")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        // Call the setters
+        // Make sure all of these methods are declared in jni-config.json file, otherwise
+        // java method not found exception will be thrown
+        let charset_name_val = jni_new_string_as_jvalue(env, &settings.char_set.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setCharsetName",
+            "(Ljava/lang/String;)V",
+            &[(&charset_name_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setMaxLength",
+            "(I)V",
+            &[JValue::Int(settings.max_length)],
+        )?;
+        let j_pdf_conf = JPDFParserConfig::new(env, settings.pdf_conf)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPdfConfig",
+            "(Lorg/apache/tika/parser/pdf/PDFParserConfig;)V",
+            &[(&j_pdf_conf.internal).into()],
+        )?;
+        let j_office_conf = JOfficeParserConfig::new(env, settings.office_conf)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOfficeConfig",
+            "(Lorg/apache/tika/parser/microsoft/OfficeParserConfig;)V",
+            &[(&j_office_conf.internal).into()],
+        )?;
+        let j_ocr_conf = JTesseractOcrConfig::new(env, settings.ocr_conf)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setTesseractConfig",
+            "(Lorg/apache/tika/parser/ocr/TesseractOCRConfig;)V",
+            &[(&j_ocr_conf.internal).into()],
+        )?;
+        let passwords_val = jni_new_string_array_as_jvalue(env, settings.passwords)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setPasswords",
+            "([Ljava/lang/String;)V",
+            &[(&passwords_val).into()],
+        )?;
+        if let Some(tika_config) = settings.tika_config {
+            jni_call_method(
+                env,
+                &obj,
+                "setTikaConfig",
+                "(Lorg/apache/tika/config/TikaConfig;)V",
+                &[JValue::Object(tika_config.internal.as_obj())],
+            )?;
+        }
+        let allowed_val = jni_new_string_array_as_jvalue(env, settings.allowed_mime_types)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setAllowedTypes",
+            "([Ljava/lang/String;)V",
+            &[(&allowed_val).into()],
+        )?;
+        let denied_val = jni_new_string_array_as_jvalue(env, settings.denied_mime_types)?;
+        jni_call_method(
+            env,
+            &obj,
+            "setDeniedTypes",
+            "([Ljava/lang/String;)V",
+            &[(&denied_val).into()],
+        )?;
+        // The OutputFormat enum names must match the Java ai.yobix.OutputFormat enum names
+        let output_format_val = jni_new_string_as_jvalue(env, &settings.output_format.to_string())?;
+        jni_call_method(
+            env,
+            &obj,
+            "setOutputFormat",
+            "(Ljava/lang/String;)V",
+            &[(&output_format_val).into()],
+        )?;
+        jni_call_method(
+            env,
+            &obj,
+            "setDetectLanguages",
+            "(Z)V",
+            &[JValue::from(settings.detect_languages)],
+        )?;
+        let monitor = JParseMonitor::new(env, settings.control)?;
+        if let Some(monitor) = &monitor {
+            jni_call_method(
+                env,
+                &obj,
+                "setMonitor",
+                "(Lai/yobix/ParseMonitor;)V",
+                &[JValue::Object(monitor.internal.as_obj())],
+            )?;
+        }
+
+        Ok(Self {
+            internal: obj,
+            monitor,
+        })
+    }
+}
+
+/// Wrapper for [`JObject`]s that contain `org.apache.tika.metadata.Metadata`, created with the
+/// hints of an [`ExtractOptions`] before the parsing
+pub(crate) struct JMetadata<'local> {
+    pub(crate) internal: JObject<'local>,
+}
+
+impl<'local> JMetadata<'local> {
+    /// Creates a new object instance of `Metadata` in the java world holding the given hints
+    /// keeps reference to the object for later use
+    pub(crate) fn new(env: &mut JNIEnv<'local>, options: &ExtractOptions) -> ExtractResult<Self> {
+        // Create the java object
+        let class = env.find_class("org/apache/tika/metadata/Metadata")?;
+        let obj = env.new_object(&class, "()V", &[])?;
+
+        // The keys are the names of the TikaCoreProperties.RESOURCE_NAME_KEY,
+        // HttpHeaders.CONTENT_TYPE and HttpHeaders.CONTENT_ENCODING properties
+        let hints = [
+            ("resourceName", &options.file_name),
+            ("Content-Type", &options.content_type),
+            ("Content-Encoding", &options.charset),
+        ];
+        for (key, value) in hints {
+            if let Some(value) = value {
+                let key_val = jni_new_string_as_jvalue(env, key)?;
+                let value_val = jni_new_string_as_jvalue(env, value)?;
+                jni_call_method(
+                    env,
+                    &obj,
+                    "set",
+                    "(Ljava/lang/String;Ljava/lang/String;)V",
+                    &[(&key_val).into(), (&value_val).into()],
+                )?;
+            }
+        }
+
+        Ok(Self { internal: obj })
+    }
+}
@@ -0,0 +1,145 @@
+use crate::errors::{Error, ExtractResult};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use std::io::BufRead;
+
+/// Classes of the `div` elements Tika wraps each page in: `page` for PDF pages and
+/// `slide-content` for PowerPoint slides
+const PAGE_CLASSES: [&str; 2] = ["page", "slide-content"];
+/// Classes of the `div` elements Tika wraps embedded documents in, their pages are not pages of
+/// the container document
+const EMBEDDED_CLASSES: [&str; 2] = ["embedded", "package-entry"];
+
+/// Event of the body of a Tika XHTML document
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum XhtmlEvent {
+    /// Start of an element, with its local name and attributes. Empty elements such as `<br/>`
+    /// are returned as a start immediately followed by an end
+    Start {
+        name: String,
+        attributes: Vec<(String, String)>,
+    },
+    /// End of an element, with its local name
+    End { name: String },
+    /// Unescaped text, including the whitespace Tika adds after block elements
+    Text(String),
+}
+
+impl XhtmlEvent {
+    /// Returns the value of the given attribute of a start event
+    pub(crate) fn attribute(&self, key: &str) -> Option<&str> {
+        match self {
+            XhtmlEvent::Start { attributes, .. } => attributes
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str()),
+            _ => None,
+        }
+    }
+
+    /// Returns true if this is the start of an element with the given class, ignoring case
+    pub(crate) fn has_class(&self, class: &str) -> bool {
+        self.attribute("class").is_some_and(|classes| {
+            classes
+                .split_whitespace()
+                .any(|c| c.eq_ignore_ascii_case(class))
+        })
+    }
+
+    /// Returns true if this is the start of the given element
+    pub(crate) fn is_start_of(&self, element: &str) -> bool {
+        matches!(self, XhtmlEvent::Start { name, .. } if name == element)
+    }
+
+    /// Returns true if this is the start of a page or a slide
+    pub(crate) fn is_page_start(&self) -> bool {
+        self.is_start_of("div") && PAGE_CLASSES.iter().any(|class| self.has_class(class))
+    }
+
+    /// Returns true if this is the start of an embedded document
+    pub(crate) fn is_embedded_start(&self) -> bool {
+        self.is_start_of("div") && EMBEDDED_CLASSES.iter().any(|class| self.has_class(class))
+    }
+}
+
+/// Pull reader over the XHTML output of Tika, yielding only the events inside `<body>`
+pub(crate) struct XhtmlReader<R: BufRead> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    in_body: bool,
+    done: bool,
+}
+
+impl<R: BufRead> XhtmlReader<R> {
+    pub(crate) fn new(inner: R) -> Self {
+        let mut reader = Reader::from_reader(inner);
+        reader.config_mut().expand_empty_elements = true;
+        Self {
+            reader,
+            buf: Vec::new(),
+            in_body: false,
+            done: false,
+        }
+    }
+
+    /// Returns the underlying reader
+    pub(crate) fn get_ref(&self) -> &R {
+        self.reader.get_ref()
+    }
+
+    /// Returns the next event of the body, or None once the document is fully read
+    pub(crate) fn next_event(&mut self) -> ExtractResult<Option<XhtmlEvent>> {
+        while !self.done {
+            self.buf.clear();
+            let event = match self.reader.read_event_into(&mut self.buf) {
+                Ok(event) => event,
+                Err(e) => {
+                    self.done = true;
+                    return Err(xml_error(e));
+                }
+            };
+
+            match event {
+                Event::Start(e) if e.local_name().as_ref() == b"body" => self.in_body = true,
+                Event::End(e) if e.local_name().as_ref() == b"body" => self.in_body = false,
+                Event::Eof => self.done = true,
+                _ if !self.in_body => {}
+                Event::Start(e) => return start_event(&e).map(Some),
+                Event::End(e) => {
+                    let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+                    return Ok(Some(XhtmlEvent::End { name }));
+                }
+                Event::Text(e) => {
+                    let text = e.unescape().map_err(xml_error)?;
+                    return Ok(Some(XhtmlEvent::Text(text.into_owned())));
+                }
+                Event::CData(e) => {
+                    let text = String::from_utf8_lossy(&e).into_owned();
+                    return Ok(Some(XhtmlEvent::Text(text)));
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+}
+
+fn start_event(e: &BytesStart) -> ExtractResult<XhtmlEvent> {
+    let name = String::from_utf8_lossy(e.local_name().as_ref()).into_owned();
+    let mut attributes = Vec::new();
+    for attribute in e.attributes() {
+        let attribute = attribute.map_err(|e| xml_error(e.into()))?;
+        let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+        let value = attribute.unescape_value().map_err(xml_error)?.into_owned();
+        attributes.push((key, value));
+    }
+    Ok(XhtmlEvent::Start { name, attributes })
+}
+
+/// Maps the xml errors, keeping the timeouts and cancellations of the underlying stream
+fn xml_error(err: quick_xml::Error) -> Error {
+    match err {
+        quick_xml::Error::Io(e) => Error::from(e.as_ref()),
+        e => Error::ParseError(format!("Invalid XHTML output: {}", e)),
+    }
+}
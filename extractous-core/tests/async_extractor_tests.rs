@@ -0,0 +1,113 @@
+#![cfg(feature = "async")]
+
+use extractous::{AsyncExtractor, Extractor};
+use std::fs;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+use tokio::io::AsyncReadExt;
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("category-level.docx", 0.9; "Test DOCX file")]
+#[test_case("simple.odt", 0.8; "Test ODT file")]
+#[tokio::test]
+async fn test_async_extract_file_to_stream(file_name: &str, target_dist: f64) {
+    let extractor = AsyncExtractor::new(Extractor::new());
+
+    let (mut reader, metadata) = extractor
+        .extract_file(&format!("../test_files/documents/{}", file_name))
+        .await
+        .unwrap();
+    let mut extracted = String::new();
+    reader.read_to_string(&mut extracted).await.unwrap();
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+    assert!(!metadata.is_empty());
+}
+
+#[tokio::test]
+async fn test_async_extract_bytes_to_stream() {
+    let extractor = AsyncExtractor::default();
+
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (mut reader, _metadata) = extractor.extract_bytes(&bytes).await.unwrap();
+    drop(bytes);
+
+    // Small reads go through the chunk buffered by the reader
+    let mut content = Vec::new();
+    let mut small = [0u8; 7];
+    loop {
+        let n = reader.read(&mut small).await.unwrap();
+        if n == 0 {
+            break;
+        }
+        content.extend_from_slice(&small[..n]);
+    }
+    let extracted = String::from_utf8_lossy(&content);
+
+    let expected =
+        fs::read_to_string("../test_files/expected_result/2022_Q3_AAPL.pdf.txt").unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_async_extract_to_string_bounded_concurrency() {
+    let extractor = AsyncExtractor::new(Extractor::new()).set_max_concurrency(2);
+    assert_eq!(extractor.max_concurrency(), 2);
+
+    let files = [
+        "2022_Q3_AAPL.pdf",
+        "category-level.docx",
+        "simple.odt",
+        "simple.doc",
+    ]
+    .map(|file_name| format!("../test_files/documents/{}", file_name));
+
+    // Driving all the futures from a single thread works since the executor is never blocked
+    let (r1, r2, r3, r4) = tokio::join!(
+        extractor.extract_file_to_string(&files[0]),
+        extractor.extract_file_to_string(&files[1]),
+        extractor.extract_file_to_string(&files[2]),
+        extractor.extract_file_to_string(&files[3]),
+    );
+    for result in [r1, r2, r3, r4] {
+        let (content, _metadata) = result.unwrap();
+        assert!(!content.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn test_async_extract_file_not_found() {
+    let extractor = AsyncExtractor::default();
+    let result = extractor
+        .extract_file("../test_files/documents/not_found.pdf")
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_async_extract_file_to_stream_finish() {
+    let extractor = AsyncExtractor::default();
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+    let (mut reader, _metadata) = extractor.extract_file(file_path).await.unwrap();
+    let mut head = [0u8; 16];
+    reader.read_exact(&mut head).await.unwrap();
+    assert!(reader.final_metadata().is_none());
+
+    let final_metadata = reader.finish().await.unwrap();
+    assert!(final_metadata.contains_key("xmpTPg:NPages"));
+}
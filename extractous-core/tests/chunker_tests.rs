@@ -0,0 +1,49 @@
+use extractous::{ChunkUnit, Chunker, Extractor};
+
+const FILE_PATH: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+#[test]
+fn test_chunk_file_stream() {
+    let extractor = Extractor::new().set_extract_string_max_length(10_000_000);
+    let chunker = Chunker::new()
+        .set_unit(ChunkUnit::Bytes)
+        .set_max_size(512)
+        .set_overlap(64);
+
+    let (stream, _metadata) = extractor.extract_file(FILE_PATH).unwrap();
+    let chunks: Vec<_> = chunker
+        .chunk_reader(stream)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert!(chunks.len() > 10);
+    assert!(chunks.iter().all(|chunk| chunk.text.len() <= 512));
+
+    // Streamed chunks are the same as the chunks of the whole text
+    let (text, _metadata) = extractor.extract_file_to_string(FILE_PATH).unwrap();
+    assert_eq!(chunks, chunker.chunk_str(&text));
+}
+
+#[test]
+fn test_chunk_file_pages() {
+    let (pages, _metadata) = Extractor::new().extract_file_to_pages(FILE_PATH).unwrap();
+    let chunks = Chunker::new().set_max_size(1000).chunk_pages(pages.clone());
+
+    // Every page has chunks, in page order
+    let mut numbers: Vec<_> = chunks
+        .iter()
+        .filter_map(|chunk| chunk.page_number)
+        .collect();
+    numbers.dedup();
+    assert_eq!(numbers, (1..=pages.len()).collect::<Vec<_>>());
+
+    for chunk in &chunks {
+        let page = &pages[chunk.page_number.unwrap() - 1];
+        let source: String = page
+            .text
+            .chars()
+            .skip(chunk.start)
+            .take(chunk.end - chunk.start)
+            .collect();
+        assert_eq!(source, chunk.text);
+    }
+}
@@ -0,0 +1,58 @@
+use extractous::{DetectionSource, Error, Extractor};
+use std::fs;
+use test_case::test_case;
+
+#[test_case("2022_Q3_AAPL.pdf", "application/pdf"; "Test PDF file")]
+#[test_case("science-exploration-1p.pptx", "application/vnd.openxmlformats-officedocument.presentationml.presentation"; "Test PPTX file")]
+#[test_case("simple.odt", "application/vnd.oasis.opendocument.text"; "Test ODT file")]
+#[test_case("table-multi-row-column-cells-actual.csv", "text/csv"; "Test CSV file")]
+#[test_case("vodafone.xlsx", "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"; "Test XLSX file")]
+#[test_case("category-level.docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document"; "Test DOCX file")]
+#[test_case("simple.doc", "application/msword"; "Test DOC file")]
+#[test_case("table-multi-row-column-cells.png", "image/png"; "Test PNG file")]
+#[test_case("winter-sports.epub", "application/epub+zip"; "Test EPUB file")]
+fn test_detect_file(file_name: &str, expected_mime_type: &str) {
+    let extractor = Extractor::new();
+    let result = extractor
+        .detect_file(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+
+    assert_eq!(result.mime_type, expected_mime_type);
+    assert_ne!(result.source, DetectionSource::Unknown);
+}
+
+#[test_case("2022_Q3_AAPL.pdf", DetectionSource::Magic; "Test PDF file")]
+#[test_case("table-multi-row-column-cells.png", DetectionSource::Magic; "Test PNG file")]
+#[test_case("table-multi-row-column-cells-actual.csv", DetectionSource::Filename; "Test CSV file")]
+fn test_detect_file_source(file_name: &str, expected_source: DetectionSource) {
+    let extractor = Extractor::new();
+    let result = extractor
+        .detect_file(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+
+    assert_eq!(result.source, expected_source);
+}
+
+#[test_case("2022_Q3_AAPL.pdf", "application/pdf", DetectionSource::Magic; "Test PDF file")]
+#[test_case("category-level.docx", "application/vnd.openxmlformats-officedocument.wordprocessingml.document", DetectionSource::Container; "Test DOCX file")]
+#[test_case("simple.doc", "application/msword", DetectionSource::Container; "Test DOC file")]
+fn test_detect_bytes(file_name: &str, expected_mime_type: &str, expected_source: DetectionSource) {
+    let extractor = Extractor::new();
+    let bytes = fs::read(format!("../test_files/documents/{}", file_name)).unwrap();
+    let result = extractor.detect_bytes(&bytes).unwrap();
+
+    assert_eq!(result.mime_type, expected_mime_type);
+    assert_eq!(result.source, expected_source);
+}
+
+#[test]
+fn test_detect_file_not_found() {
+    let extractor = Extractor::new();
+    let file_path = "../test_files/documents/does-not-exist.pdf";
+    let result = extractor.detect_file(file_path);
+
+    match result {
+        Err(Error::FileNotFound(context)) => assert_eq!(context.path.as_deref(), Some(file_path)),
+        other => panic!("Unexpected result: {:?}", other.map(|r| r.mime_type)),
+    }
+}
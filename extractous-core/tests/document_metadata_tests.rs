@@ -0,0 +1,31 @@
+use extractous::{DocumentMetadata, Extractor};
+
+#[test]
+fn test_document_metadata_pdf() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+
+    let doc = DocumentMetadata::from(&metadata);
+    assert_eq!(doc.title.as_deref(), Some("0000320193-22-000070"));
+    assert_eq!(doc.page_count, Some(28));
+    assert_eq!(doc.content_type.as_deref(), Some("application/pdf"));
+    assert_eq!(doc.encrypted, Some(true));
+    assert!(doc.created.is_some());
+    assert_eq!(doc.raw, metadata);
+}
+
+#[test]
+fn test_document_metadata_docx() {
+    let extractor = Extractor::new();
+    let (_content, metadata) = extractor
+        .extract_file_to_string("../test_files/documents/category-level.docx")
+        .unwrap();
+
+    let doc = DocumentMetadata::from(metadata);
+    assert!(doc.page_count.is_some());
+    assert!(doc.word_count.is_some());
+    assert!(doc.producer.is_some());
+    assert_eq!(doc.encrypted, None);
+}
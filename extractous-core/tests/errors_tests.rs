@@ -0,0 +1,52 @@
+use extractous::{Error, Extractor};
+use std::io::Read;
+
+const MISSING_FILE: &str = "../test_files/documents/does-not-exist.pdf";
+
+fn assert_file_not_found<T>(result: Result<T, Error>) {
+    match result {
+        Err(Error::FileNotFound(context)) => {
+            assert_eq!(context.path.as_deref(), Some(MISSING_FILE));
+            assert_eq!(
+                context.java_class.as_deref(),
+                Some("java.nio.file.NoSuchFileException")
+            );
+            assert!(context.stack_trace.is_some());
+        }
+        Err(other) => panic!("Unexpected error: {:?}", other),
+        Ok(_) => panic!("Expected an error"),
+    }
+}
+
+#[test]
+fn test_file_not_found() {
+    let extractor = Extractor::new();
+    assert_file_not_found(extractor.extract_file(MISSING_FILE));
+    assert_file_not_found(extractor.extract_file_to_string(MISSING_FILE));
+    assert_file_not_found(extractor.detect_file(MISSING_FILE));
+}
+
+#[test]
+fn test_zero_byte_file() {
+    let extractor = Extractor::new();
+    assert!(matches!(
+        extractor.extract_bytes_to_string(&[]),
+        Err(Error::ZeroByteFile(_))
+    ));
+
+    let (mut stream, _metadata) = extractor.extract_bytes(&[]).unwrap();
+    let err = Error::from(stream.read_to_end(&mut Vec::new()).unwrap_err());
+    assert!(matches!(err, Error::ZeroByteFile(_)));
+}
+
+#[test]
+fn test_corrupt_document() {
+    let result = Extractor::new().extract_bytes_to_string(b"%PDF-1.7\nnot a pdf");
+    match result {
+        Err(Error::CorruptDocument(context)) => {
+            assert_eq!(context.path, None);
+            assert_eq!(context.mime_type.as_deref(), Some("application/pdf"));
+        }
+        other => panic!("Unexpected result: {:?}", other),
+    }
+}
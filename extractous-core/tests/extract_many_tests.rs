@@ -0,0 +1,80 @@
+use extractous::{BatchOrder, BatchSource, Extractor};
+use std::fs;
+
+const FILES: [&str; 5] = [
+    "2022_Q3_AAPL.pdf",
+    "category-level.docx",
+    "not_found.pdf",
+    "simple.odt",
+    "simple.doc",
+];
+
+fn file_paths() -> Vec<String> {
+    FILES
+        .iter()
+        .map(|file_name| format!("../test_files/documents/{}", file_name))
+        .collect()
+}
+
+#[test]
+fn test_extract_many_input_order() {
+    let extractor = Extractor::new();
+
+    let items: Vec<_> = extractor
+        .extract_many(file_paths(), 2, BatchOrder::Input)
+        .collect();
+
+    assert_eq!(items.len(), FILES.len());
+    for (index, item) in items.iter().enumerate() {
+        assert_eq!(item.index, index);
+        assert_eq!(item.source, file_paths()[index]);
+    }
+
+    // The missing file fails alone, without aborting the batch
+    assert!(items[2].result.is_err());
+    for item in items.iter().filter(|item| item.index != 2) {
+        let (content, _metadata) = item.result.as_ref().unwrap();
+        assert!(!content.is_empty(), "Empty content for {}", item.source);
+    }
+}
+
+#[test]
+fn test_extract_many_completion_order() {
+    let extractor = Extractor::new();
+
+    let mut items: Vec<_> = extractor
+        .extract_many(file_paths(), 3, BatchOrder::Completion)
+        .collect();
+    assert_eq!(items.len(), FILES.len());
+
+    // Every source is returned exactly once, whatever the order
+    items.sort_by_key(|item| item.index);
+    for (index, item) in items.iter().enumerate() {
+        assert_eq!(item.index, index);
+        assert_eq!(item.result.is_err(), index == 2);
+    }
+}
+
+#[test]
+fn test_extract_many_same_content_as_single_extraction() {
+    let extractor = Extractor::new();
+
+    let path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let sources = vec![
+        BatchSource::File(path.to_string()),
+        BatchSource::Bytes {
+            id: "aapl-bytes".to_string(),
+            buffer: fs::read(path).unwrap(),
+        },
+    ];
+    let items: Vec<_> = extractor
+        .extract_many(sources, 2, BatchOrder::Input)
+        .collect();
+
+    let (expected, _metadata) = extractor.extract_file_to_string(path).unwrap();
+    assert_eq!(items[1].source, "aapl-bytes");
+    for item in items {
+        let (content, _metadata) = item.result.unwrap();
+        assert_eq!(content, expected);
+    }
+}
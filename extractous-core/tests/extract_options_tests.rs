@@ -0,0 +1,115 @@
+use extractous::{DetectionSource, ExtractOptions, Extractor, Metadata};
+use std::fs::File;
+use std::io::Read;
+
+const CSV: &str = "../test_files/documents/table-multi-row-column-cells-actual.csv";
+
+fn content_type(metadata: &Metadata) -> &str {
+    &metadata.get("Content-Type").unwrap()[0]
+}
+
+#[test]
+fn test_file_name_hint() {
+    let buffer = std::fs::read(CSV).unwrap();
+    let extractor = Extractor::new();
+
+    // The content alone looks like plain text
+    let (_content, metadata) = extractor.extract_bytes_to_string(&buffer).unwrap();
+    assert!(content_type(&metadata).starts_with("text/plain"));
+
+    let options = ExtractOptions {
+        file_name: Some("table.csv".to_string()),
+        ..Default::default()
+    };
+    let (content, metadata) = extractor
+        .extract_bytes_to_string_with(&buffer, &options)
+        .unwrap();
+    assert!(!content.trim().is_empty());
+    assert!(content_type(&metadata).starts_with("text/csv"));
+    assert_eq!(metadata.get("resourceName").unwrap()[0], "table.csv");
+
+    let documents = extractor
+        .extract_bytes_recursive_with(&buffer, &options)
+        .unwrap();
+    assert!(content_type(&documents[0].metadata).starts_with("text/csv"));
+}
+
+#[test]
+fn test_detect_file_name_hint() {
+    let buffer = std::fs::read(CSV).unwrap();
+    let extractor = Extractor::new();
+
+    let result = extractor.detect_bytes(&buffer).unwrap();
+    assert!(result.mime_type.starts_with("text/plain"));
+
+    let options = ExtractOptions {
+        file_name: Some("table.csv".to_string()),
+        ..Default::default()
+    };
+    let result = extractor.detect_bytes_with(&buffer, &options).unwrap();
+    assert!(result.mime_type.starts_with("text/csv"));
+    assert_eq!(result.source, DetectionSource::Filename);
+}
+
+#[test]
+fn test_tables_file_name_hint() {
+    let buffer = std::fs::read(CSV).unwrap();
+    let extractor = Extractor::new();
+
+    // Only the csv parser outputs a table
+    let (tables, _metadata) = extractor.extract_bytes_to_tables(&buffer).unwrap();
+    assert!(tables.is_empty());
+
+    let options = ExtractOptions {
+        file_name: Some("table.csv".to_string()),
+        ..Default::default()
+    };
+    let (tables, metadata) = extractor
+        .extract_bytes_to_tables_with(&buffer, &options)
+        .unwrap();
+    assert_eq!(tables.len(), 1);
+    assert!(content_type(&metadata).starts_with("text/csv"));
+}
+
+#[test]
+fn test_content_type_hint() {
+    let buffer = std::fs::read(CSV).unwrap();
+    let options = ExtractOptions {
+        content_type: Some("text/csv".to_string()),
+        ..Default::default()
+    };
+    let (mut stream, metadata) = Extractor::new()
+        .extract_bytes_with(&buffer, &options)
+        .unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert!(!content.trim().is_empty());
+    assert!(content_type(&metadata).starts_with("text/csv"));
+}
+
+#[test]
+fn test_reader_hints() {
+    let options = ExtractOptions {
+        file_name: Some("table.csv".to_string()),
+        ..Default::default()
+    };
+    let (content, metadata) = Extractor::new()
+        .extract_reader_to_string_with(File::open(CSV).unwrap(), &options)
+        .unwrap();
+    assert!(!content.trim().is_empty());
+    assert!(content_type(&metadata).starts_with("text/csv"));
+}
+
+#[test]
+fn test_charset_hint() {
+    // "café crème" in ISO-8859-1
+    let buffer = b"caf\xe9 cr\xe8me\n";
+    let options = ExtractOptions {
+        charset: Some("ISO-8859-1".to_string()),
+        ..Default::default()
+    };
+    let (content, _metadata) = Extractor::new()
+        .extract_bytes_to_string_with(buffer, &options)
+        .unwrap();
+    assert_eq!(content.trim(), "café crème");
+}
@@ -0,0 +1,100 @@
+use extractous::Extractor;
+use std::fs;
+use std::fs::File;
+use std::io::{self, Read};
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+/// Reader that returns at most `chunk_size` bytes per read, like a socket would
+struct ChunkedReader<R> {
+    inner: R,
+    chunk_size: usize,
+}
+
+impl<R: Read> Read for ChunkedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = buf.len().min(self.chunk_size);
+        self.inner.read(&mut buf[..len])
+    }
+}
+
+/// Reader that fails after returning a few bytes
+struct FailingReader {
+    remaining: usize,
+}
+
+impl Read for FailingReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.remaining == 0 {
+            return Err(io::Error::new(io::ErrorKind::ConnectionReset, "connection reset"));
+        }
+        let len = buf.len().min(self.remaining);
+        buf[..len].fill(b'a');
+        self.remaining -= len;
+        Ok(len)
+    }
+}
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("science-exploration-1p.pptx", 0.9; "Test PPTX file")]
+#[test_case("simple.odt", 0.8; "Test ODT file")]
+#[test_case("vodafone.xlsx", 0.4; "Test XLSX file")]
+#[test_case("category-level.docx", 0.9; "Test DOCX file")]
+#[test_case("simple.doc", 0.9; "Test DOC file")]
+fn test_extract_reader_to_stream(file_name: &str, target_dist: f64) {
+    let extractor = Extractor::new();
+
+    let file = File::open(format!("../test_files/documents/{}", file_name)).unwrap();
+    let reader = ChunkedReader {
+        inner: file,
+        chunk_size: 1000,
+    };
+    let (mut stream, metadata) = extractor.extract_reader(reader).unwrap();
+
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    let extracted = String::from_utf8_lossy(&buffer);
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+    assert!(!metadata.is_empty());
+}
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("category-level.docx", 0.9; "Test DOCX file")]
+#[test_case("table-multi-row-column-cells-actual.csv", 0.8; "Test CSV file")]
+fn test_extract_reader_to_string(file_name: &str, target_dist: f64) {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+
+    // A borrowed reader can be used when extracting to a string
+    let mut file = File::open(format!("../test_files/documents/{}", file_name)).unwrap();
+    let (extracted, metadata) = extractor.extract_reader_to_string(&mut file).unwrap();
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+    assert!(!metadata.is_empty());
+}
+
+#[test]
+fn test_extract_reader_to_string_read_error() {
+    let extractor = Extractor::new();
+    let result = extractor.extract_reader_to_string(FailingReader { remaining: 100 });
+
+    assert!(result.is_err());
+}
@@ -0,0 +1,49 @@
+use extractous::Extractor;
+use std::fs;
+use test_case::test_case;
+use textdistance::nstr::cosine;
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("science-exploration-1p.pptx", 0.9; "Test PPTX file")]
+#[test_case("category-level.docx", 0.9; "Test DOCX file")]
+#[test_case("simple.doc", 0.9; "Test DOC file")]
+fn test_extract_file_recursive(file_name: &str, target_dist: f64) {
+    let extractor = Extractor::new().set_extract_string_max_length(1000000);
+    let documents = extractor
+        .extract_file_recursive(&format!("../test_files/documents/{}", file_name))
+        .unwrap();
+
+    // The container document always comes first
+    let container = documents.first().unwrap();
+    assert_eq!(container.depth, 0);
+    assert_eq!(container.embedded_path, None);
+    assert!(!container.metadata.is_empty());
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let dist = cosine(expected.trim(), container.content.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+
+    // Embedded documents know where they come from
+    for document in documents.iter().skip(1) {
+        assert!(document.depth >= 1);
+        let embedded_path = document.embedded_path.as_ref().unwrap();
+        assert!(embedded_path.starts_with('/'));
+    }
+}
+
+#[test]
+fn test_extract_bytes_recursive() {
+    let extractor = Extractor::new();
+    let bytes = fs::read("../test_files/issue-58-smartart.pptx").unwrap();
+    let documents = extractor.extract_bytes_recursive(&bytes).unwrap();
+
+    assert_eq!(documents[0].depth, 0);
+    assert!(documents.iter().skip(1).all(|d| d.embedded_path.is_some()));
+}
@@ -0,0 +1,63 @@
+use extractous::{ElementKind, Extractor};
+use std::fs;
+
+#[test]
+fn test_extract_file_to_elements_docx() {
+    let extractor = Extractor::new();
+    let (elements, _metadata) = extractor
+        .extract_file_to_elements("../test_files/documents/category-level.docx")
+        .unwrap();
+
+    let heading = elements
+        .iter()
+        .position(|element| element.text == "A Heading 1")
+        .unwrap();
+    assert_eq!(elements[heading].kind, ElementKind::Heading);
+    assert_eq!(elements[heading].heading_level, Some(1));
+
+    let sub_heading = elements
+        .iter()
+        .position(|element| element.text == "A Heading 2")
+        .unwrap();
+    assert_eq!(elements[sub_heading].heading_level, Some(2));
+    assert_eq!(elements[sub_heading].parent_section, Some(heading));
+
+    // Elements after a heading belong to its section
+    assert_eq!(elements[heading + 1].parent_section, Some(heading));
+    assert_eq!(elements[0].parent_section, None);
+    assert!(elements.iter().all(|element| element.page_number.is_none()));
+}
+
+#[test]
+fn test_extract_file_to_elements_pdf_pages() {
+    let extractor = Extractor::new();
+    let (elements, _metadata) = extractor
+        .extract_file_to_elements("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+
+    let page_breaks = elements
+        .iter()
+        .filter(|element| element.kind == ElementKind::PageBreak)
+        .count();
+    assert_eq!(page_breaks, 27);
+    assert_eq!(elements.first().unwrap().page_number, Some(1));
+    assert_eq!(elements.last().unwrap().page_number, Some(28));
+    assert!(elements
+        .iter()
+        .filter(|element| element.kind != ElementKind::PageBreak)
+        .all(|element| !element.text.is_empty()));
+}
+
+#[test]
+fn test_extract_bytes_to_elements_table() {
+    let bytes = fs::read("../test_files/documents/vodafone.xlsx").unwrap();
+    let extractor = Extractor::new();
+    let (elements, _metadata) = extractor.extract_bytes_to_elements(&bytes).unwrap();
+
+    let table = elements
+        .iter()
+        .find(|element| element.kind == ElementKind::Table)
+        .unwrap();
+    assert!(table.text.lines().count() > 1);
+    assert!(table.text.contains('\t'));
+}
@@ -0,0 +1,64 @@
+use extractous::{CharSet, Error, Extractor, OutputFormat};
+use std::io::Read;
+
+#[test]
+fn test_extract_file_to_markdown_string() {
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let (markdown, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/category-level.docx")
+        .unwrap();
+
+    assert!(markdown.contains("# A Heading 1\n"));
+    assert!(markdown.contains("## A Heading 2\n"));
+    assert!(markdown.contains("A top level list item"));
+    assert!(!markdown.contains("<p>"));
+}
+
+#[test]
+fn test_extract_file_to_markdown_stream() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new()
+        .set_output_format(OutputFormat::Markdown)
+        .set_extract_string_max_length(10_000_000);
+
+    let (mut stream, _metadata) = extractor.extract_file(file_path).unwrap();
+    let mut markdown = String::new();
+    stream.read_to_string(&mut markdown).unwrap();
+
+    // One separator between each of the 28 pages
+    let separators = markdown.lines().filter(|line| *line == "---").count();
+    assert_eq!(separators, 27);
+
+    let (expected, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(markdown, expected);
+}
+
+#[test]
+fn test_extract_file_to_markdown_table() {
+    let extractor = Extractor::new().set_output_format(OutputFormat::Markdown);
+    let (markdown, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/table-multi-row-column-cells-actual.csv")
+        .unwrap();
+
+    let lines: Vec<_> = markdown.lines().collect();
+    assert!(lines[0].starts_with("| Disability Category |"));
+    assert!(lines[1].starts_with("| --- |"));
+}
+
+#[test]
+fn test_extract_file_to_markdown_stream_encoding() {
+    let file_path = "../test_files/documents/category-level.docx";
+    let result = Extractor::new()
+        .set_output_format(OutputFormat::Markdown)
+        .set_encoding(CharSet::WINDOWS_1252)
+        .extract_file(file_path);
+    assert!(matches!(result, Err(Error::UnsupportedEncoding(_))));
+
+    // The encoding is only used by the streams
+    let (markdown, _metadata) = Extractor::new()
+        .set_output_format(OutputFormat::Markdown)
+        .set_encoding(CharSet::WINDOWS_1252)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    assert!(markdown.contains("# A Heading 1\n"));
+}
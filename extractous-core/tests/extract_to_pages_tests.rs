@@ -0,0 +1,66 @@
+use extractous::Extractor;
+use std::fs;
+use textdistance::nstr::cosine;
+
+#[test]
+fn test_extract_file_to_pages_pdf() {
+    let extractor = Extractor::new();
+    let (pages, metadata) = extractor
+        .extract_file_to_pages("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+
+    assert_eq!(pages.len(), 28);
+    assert_eq!(
+        metadata.get("xmpTPg:NPages").unwrap(),
+        &vec!["28".to_string()]
+    );
+    for (index, page) in pages.iter().enumerate() {
+        assert_eq!(page.number, index + 1);
+    }
+    assert!(pages[0].text.contains("UNITED STATES"));
+
+    // All the pages together hold the whole text
+    let expected =
+        fs::read_to_string("../test_files/expected_result/2022_Q3_AAPL.pdf.txt").unwrap();
+    let all_pages: Vec<_> = pages.into_iter().map(|page| page.text).collect();
+    let dist = cosine(expected.trim(), all_pages.join("\n").trim());
+    assert!(dist > 0.9, "Cosine similarity is too low: {}", dist);
+}
+
+#[test]
+fn test_extract_file_to_pages_pptx() {
+    let extractor = Extractor::new();
+    let (pages, _metadata) = extractor
+        .extract_file_to_pages("../test_files/documents/simple.pptx")
+        .unwrap();
+
+    assert_eq!(pages.len(), 2);
+    assert!(pages[0].text.contains("Title Slide"));
+    assert!(pages[1].text.contains("Things to think about"));
+}
+
+#[test]
+fn test_extract_file_to_pages_without_page_markers() {
+    let extractor = Extractor::new();
+    let (pages, _metadata) = extractor
+        .extract_file_to_pages("../test_files/documents/category-level.docx")
+        .unwrap();
+
+    assert_eq!(pages.len(), 1);
+    assert!(!pages[0].text.is_empty());
+}
+
+#[test]
+fn test_extract_bytes_to_page_iter() {
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let extractor = Extractor::new();
+    let (pages, _metadata) = extractor.extract_bytes_to_page_iter(&bytes).unwrap();
+
+    // Stop reading after the first pages
+    let first_pages: Vec<_> = pages.take(3).map(|page| page.unwrap()).collect();
+    let numbers: Vec<_> = first_pages.iter().map(|page| page.number).collect();
+    assert_eq!(numbers, vec![1, 2, 3]);
+
+    let (all_pages, _metadata) = extractor.extract_bytes_to_pages(&bytes).unwrap();
+    assert_eq!(first_pages[..], all_pages[..3]);
+}
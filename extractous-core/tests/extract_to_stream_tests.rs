@@ -1,4 +1,4 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use extractous::{CharSet, Error, Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
 use std::fs;
 use std::io::Read;
 use test_case::test_case;
@@ -86,3 +86,157 @@ fn test_extract_bytes_to_stream_ara_ocr_png() {
     );
     println!("{}: {}", "ara-ocr.png", dist);
 }
+
+#[test_case("2022_Q3_AAPL.pdf", 0.9; "Test PDF file")]
+#[test_case("category-level.docx", 0.9; "Test DOCX file")]
+fn test_extract_bytes_to_stream_source_dropped(file_name: &str, target_dist: f64) {
+    let extractor = Extractor::new();
+
+    // The source buffer is dropped, and its memory overwritten, before the stream is read
+    let mut bytes = fs::read(format!("../test_files/documents/{}", file_name)).unwrap();
+    let (mut stream, _metadata) = extractor.extract_bytes(&bytes).unwrap();
+    bytes.fill(0);
+    drop(bytes);
+
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    let extracted = String::from_utf8_lossy(&buffer);
+
+    let expected =
+        fs::read_to_string(format!("../test_files/expected_result/{}.txt", file_name)).unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > target_dist,
+        "Cosine similarity is less than {} for file: {}, dist: {}",
+        target_dist,
+        file_name,
+        dist
+    );
+}
+
+#[test]
+fn test_extract_vec_to_stream() {
+    let extractor = Extractor::new();
+
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (mut stream, _metadata) = extractor.extract_vec(bytes).unwrap();
+
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    let extracted = String::from_utf8_lossy(&buffer);
+
+    let expected =
+        fs::read_to_string("../test_files/expected_result/2022_Q3_AAPL.pdf.txt").unwrap();
+    let dist = cosine(expected.trim(), extracted.trim());
+    assert!(
+        dist > 0.9,
+        "Cosine similarity is less than 0.9, dist: {}",
+        dist
+    );
+}
+
+#[test]
+fn test_extract_vec_to_stream_dropped_before_read() {
+    let extractor = Extractor::new();
+
+    // Dropping the stream early must not stop the parser from owning its input
+    let bytes = fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap();
+    let (mut stream, _metadata) = extractor.extract_vec(bytes).unwrap();
+    let mut head = [0u8; 16];
+    stream.read_exact(&mut head).unwrap();
+    drop(stream);
+
+    // The extractor is still usable afterwards
+    let (text, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/2022_Q3_AAPL.pdf")
+        .unwrap();
+    assert!(!text.is_empty());
+}
+
+fn extract_file_to_bytes(extractor: &Extractor, file_path: &str) -> Vec<u8> {
+    let (mut stream, _metadata) = extractor.extract_file(file_path).unwrap();
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+    buffer
+}
+
+#[test]
+fn test_extract_file_to_stream_encodings() {
+    let file_path = "../test_files/documents/winter-sports.epub";
+    let utf8 = extract_file_to_bytes(&Extractor::new(), file_path);
+    let expected = String::from_utf8(utf8).unwrap();
+
+    let utf16 = extract_file_to_bytes(&Extractor::new().set_encoding(CharSet::UTF_16LE), file_path);
+    let units: Vec<u16> = utf16
+        .chunks_exact(2)
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .collect();
+    assert_eq!(String::from_utf16(&units).unwrap(), expected);
+
+    // Latin-1 maps each byte to the char of the same code, others are replaced with '?'
+    let latin1 = extract_file_to_bytes(
+        &Extractor::new().set_encoding(CharSet::ISO_8859_1),
+        file_path,
+    );
+    let latin1: String = latin1.iter().map(|&b| b as char).collect();
+    let expected_latin1: String = expected
+        .chars()
+        .map(|c| if (c as u32) < 0x100 { c } else { '?' })
+        .collect();
+    assert_eq!(latin1, expected_latin1);
+}
+
+#[test]
+fn test_extract_file_to_stream_custom_encoding() {
+    let file_path = "../test_files/documents/winter-sports.epub";
+    let extractor = Extractor::new().set_encoding(CharSet::Custom("windows-1251".to_string()));
+    assert!(!extract_file_to_bytes(&extractor, file_path).is_empty());
+
+    let result = Extractor::new()
+        .set_encoding(CharSet::Custom("not-a-charset".to_string()))
+        .extract_file(file_path);
+    assert!(matches!(result, Err(Error::UnsupportedEncoding(_))));
+}
+
+#[test]
+fn test_extract_file_to_stream_final_metadata() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new();
+    let (_content, string_metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    let (mut stream, metadata) = extractor.extract_file(file_path).unwrap();
+    assert!(stream.final_metadata().is_none());
+    let mut buffer = Vec::new();
+    stream.read_to_end(&mut buffer).unwrap();
+
+    // The page count is only known once the parsing is done
+    let final_metadata = stream.final_metadata().unwrap();
+    assert!(final_metadata.len() > metadata.len());
+    assert_eq!(
+        final_metadata.get("xmpTPg:NPages"),
+        string_metadata.get("xmpTPg:NPages")
+    );
+
+    let (stream, _metadata) = extractor.extract_file(file_path).unwrap();
+    assert_eq!(&stream.finish().unwrap(), final_metadata);
+}
+
+#[test]
+fn test_extract_bytes_to_stream_parse_error() {
+    let (mut stream, _metadata) = Extractor::new()
+        .extract_bytes(b"%PDF-1.7\nnot a pdf")
+        .unwrap();
+
+    let mut buffer = Vec::new();
+    let err = stream.read_to_end(&mut buffer).unwrap_err();
+    match err.get_ref().and_then(|e| e.downcast_ref::<Error>()) {
+        Some(Error::CorruptDocument(context)) => {
+            assert_eq!(
+                context.java_class.as_deref(),
+                Some("org.apache.tika.exception.TikaException")
+            );
+            assert_eq!(context.path, None);
+        }
+        other => panic!("Unexpected error: {:?}", other),
+    }
+}
@@ -1,4 +1,6 @@
-use extractous::{Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig};
+use extractous::{
+    DocumentMetadata, Error, Extractor, PdfOcrStrategy, PdfParserConfig, TesseractOcrConfig,
+};
 use std::fs;
 use test_case::test_case;
 use textdistance::nstr::cosine;
@@ -115,3 +117,48 @@ fn test_test_extract_file_to_string_no_ocr_strategy_deu_ocr_pdf() {
 
     assert_eq!("", extracted.trim())
 }
+
+#[test]
+fn test_extract_file_to_string_truncated() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new().set_extract_string_max_length(100);
+    let (extracted, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    assert!(extracted.truncated);
+    assert!(extracted.chars().filter(|c| !c.is_whitespace()).count() <= 100);
+    assert!(DocumentMetadata::from(metadata).truncated);
+
+    let result = extractor
+        .set_extract_string_strict(true)
+        .extract_file_to_string(file_path);
+    match result {
+        Err(Error::WriteLimitReached(context)) => {
+            assert_eq!(context.mime_type.as_deref(), Some("application/pdf"))
+        }
+        other => panic!(
+            "Unexpected result: {:?}",
+            other.map(|(_, metadata)| metadata)
+        ),
+    }
+}
+
+#[test]
+fn test_extract_file_to_string_unlimited() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    // A limit of 10_000 characters cuts the text of this document
+    let (limited, _metadata) = Extractor::new()
+        .set_extract_string_max_length(10_000)
+        .extract_file_to_string(file_path)
+        .unwrap();
+    assert!(limited.truncated);
+
+    let extractor = Extractor::new()
+        .set_extract_string_max_length(None)
+        .set_extract_string_strict(true);
+    let (extracted, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+
+    assert!(!extracted.truncated);
+    assert!(!DocumentMetadata::from(metadata).truncated);
+    assert!(extracted.chars().count() > 10_000);
+    assert!(extracted.starts_with(&limited.content));
+}
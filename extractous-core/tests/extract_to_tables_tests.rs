@@ -0,0 +1,43 @@
+use extractous::Extractor;
+use std::fs;
+
+#[test]
+fn test_extract_file_to_tables_csv() {
+    let extractor = Extractor::new();
+    let (tables, _metadata) = extractor
+        .extract_file_to_tables("../test_files/documents/table-multi-row-column-cells-actual.csv")
+        .unwrap();
+
+    assert_eq!(tables.len(), 1);
+    let table = &tables[0];
+    let grid = table.to_grid();
+    assert_eq!(grid[0][0], "Disability Category");
+    assert_eq!(grid[2][0], "Blind");
+    assert_eq!(grid[2][4], "34.5%, n=1");
+    // Every row is laid out on the same grid
+    assert!(grid.iter().all(|row| row.len() == table.num_columns()));
+    assert_eq!(table.page_number, None);
+}
+
+#[test]
+fn test_extract_bytes_to_tables_xlsx() {
+    let bytes = fs::read("../test_files/documents/vodafone.xlsx").unwrap();
+    let extractor = Extractor::new();
+    let (tables, _metadata) = extractor.extract_bytes_to_tables(&bytes).unwrap();
+
+    assert!(!tables.is_empty());
+    for table in &tables {
+        // Each sheet is a page, named after the sheet
+        assert!(table.page_number.is_some());
+        assert!(table.page_title.is_some());
+        assert!(!table.rows.is_empty());
+    }
+
+    // Tables point to their element
+    let (elements, _metadata) = extractor.extract_bytes_to_elements(&bytes).unwrap();
+    for table in &tables {
+        let element = &elements[table.element_index];
+        assert_eq!(element.page_number, table.page_number);
+        assert!(element.text.contains(&table.rows[0][0].text));
+    }
+}
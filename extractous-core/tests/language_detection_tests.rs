@@ -0,0 +1,75 @@
+use extractous::{DocumentMetadata, Extractor, OutputFormat};
+
+const FILE_PATH: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+#[test]
+fn test_detect_languages() {
+    let extractor = Extractor::new();
+
+    let languages = extractor
+        .detect_languages("Die Katze schläft auf dem warmen Sofa neben dem Fenster.")
+        .unwrap();
+    assert_eq!(languages[0].language, "de");
+    assert!(languages[0].confidence > 0.5 && languages[0].confidence <= 1.0);
+    assert!(languages
+        .windows(2)
+        .all(|w| w[0].confidence >= w[1].confidence));
+
+    assert!(extractor.detect_languages("").unwrap().is_empty());
+}
+
+#[test]
+fn test_extract_file_to_string_detects_languages() {
+    let (_content, metadata) = Extractor::new()
+        .set_detect_languages(true)
+        .extract_file_to_string(FILE_PATH)
+        .unwrap();
+    let document_metadata = DocumentMetadata::from(metadata);
+    assert_eq!(document_metadata.detected_languages[0].language, "en");
+
+    // Disabled by default
+    let (_content, metadata) = Extractor::new().extract_file_to_string(FILE_PATH).unwrap();
+    assert!(DocumentMetadata::from(metadata)
+        .detected_languages
+        .is_empty());
+}
+
+#[test]
+fn test_extract_file_to_pages_detects_languages() {
+    let (pages, _metadata) = Extractor::new()
+        .set_detect_languages(true)
+        .extract_file_to_pages(FILE_PATH)
+        .unwrap();
+    assert_eq!(pages[0].languages[0].language, "en");
+
+    let documents = Extractor::new()
+        .set_detect_languages(true)
+        .extract_file_recursive(FILE_PATH)
+        .unwrap();
+    let document_metadata = DocumentMetadata::from(&documents[0].metadata);
+    assert_eq!(document_metadata.detected_languages[0].language, "en");
+}
+
+#[test]
+fn test_detect_languages_ignores_markup() {
+    let detected_languages = |format: OutputFormat| {
+        let (_content, metadata) = Extractor::new()
+            .set_detect_languages(true)
+            .set_output_format(format)
+            .extract_file_to_string(FILE_PATH)
+            .unwrap();
+        DocumentMetadata::from(metadata).detected_languages
+    };
+
+    // The languages are detected on the text of the body, not on the output
+    let expected = detected_languages(OutputFormat::PlainText);
+    assert_eq!(expected[0].language, "en");
+    for format in [
+        OutputFormat::Xhtml,
+        OutputFormat::Html,
+        OutputFormat::Json,
+        OutputFormat::Markdown,
+    ] {
+        assert_eq!(detected_languages(format), expected, "{format}");
+    }
+}
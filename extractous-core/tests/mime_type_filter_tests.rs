@@ -0,0 +1,79 @@
+use extractous::{Error, Extractor};
+use std::io::Read;
+
+const PDF: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+const PPTX_WITH_IMAGES: &str = "../test_files/documents/science-exploration-1p.pptx";
+
+fn assert_rejected<T>(result: Result<T, Error>, mime_type: &str) {
+    match result {
+        Err(Error::Rejected(context)) => {
+            assert_eq!(context.mime_type.as_deref(), Some(mime_type));
+        }
+        Err(e) => panic!("expected a rejected document, got {:?}", e),
+        Ok(_) => panic!("expected a rejected document"),
+    }
+}
+
+#[test]
+fn test_denied_mime_types() {
+    let extractor = Extractor::new().set_denied_mime_types(&["application/pdf"]);
+    assert_rejected(extractor.extract_file_to_string(PDF), "application/pdf");
+    assert_rejected(extractor.extract_file_recursive(PDF), "application/pdf");
+
+    // Wildcards match every subtype
+    let extractor = Extractor::new().set_denied_mime_types(&["application/*"]);
+    assert_rejected(extractor.extract_file_to_string(PDF), "application/pdf");
+
+    let extractor = Extractor::new().set_denied_mime_types(&["image/*", "audio/*"]);
+    let (content, _metadata) = extractor.extract_file_to_string(PDF).unwrap();
+    assert!(!content.trim().is_empty());
+}
+
+#[test]
+fn test_allowed_mime_types() {
+    let extractor = Extractor::new().set_allowed_mime_types(&["text/*"]);
+    assert_rejected(extractor.extract_file_to_string(PDF), "application/pdf");
+
+    let extractor = Extractor::new().set_allowed_mime_types(&["text/*", "Application/PDF"]);
+    let (content, _metadata) = extractor.extract_file_to_string(PDF).unwrap();
+    assert!(!content.trim().is_empty());
+
+    // Denied types take precedence
+    let extractor = extractor.set_denied_mime_types(&["application/pdf"]);
+    assert_rejected(extractor.extract_file_to_string(PDF), "application/pdf");
+}
+
+#[test]
+fn test_rejected_stream() {
+    let extractor = Extractor::new().set_denied_mime_types(&["application/pdf"]);
+    let (mut stream, _metadata) = extractor.extract_file(PDF).unwrap();
+
+    // The parsing runs in the background, the rejection is reported by the read
+    let mut content = String::new();
+    let err = stream.read_to_string(&mut content).unwrap_err();
+    assert!(content.is_empty());
+    assert_rejected::<()>(Err(Error::from(err)), "application/pdf");
+}
+
+#[test]
+fn test_rejected_embedded_documents_are_skipped() {
+    let extractor = Extractor::new().set_denied_mime_types(&["image/*"]);
+    let documents = extractor.extract_file_recursive(PPTX_WITH_IMAGES).unwrap();
+    assert!(!documents[0].content.trim().is_empty());
+
+    let images: Vec<_> = documents
+        .iter()
+        .filter(|d| {
+            d.metadata
+                .get("Content-Type")
+                .is_some_and(|types| types[0].starts_with("image/"))
+        })
+        .collect();
+    assert!(!images.is_empty());
+    for image in images {
+        assert!(image.metadata.iter().any(|(key, values)| {
+            key.starts_with("X-TIKA:EXCEPTION")
+                && values.iter().any(|v| v.contains("are not allowed"))
+        }));
+    }
+}
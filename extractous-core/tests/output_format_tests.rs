@@ -0,0 +1,62 @@
+use extractous::{Extractor, Metadata, OutputFormat};
+use std::io::Read;
+use test_case::test_case;
+
+const FILE_PATH: &str = "../test_files/documents/category-level.docx";
+
+fn extract_to_string(format: OutputFormat) -> String {
+    let extractor = Extractor::new().set_output_format(format);
+    let (content, _metadata) = extractor.extract_file_to_string(FILE_PATH).unwrap();
+    content.into_string()
+}
+
+fn extract_to_stream(format: OutputFormat) -> String {
+    let extractor = Extractor::new().set_output_format(format);
+    let (mut stream, _metadata) = extractor.extract_file(FILE_PATH).unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    content
+}
+
+#[test_case(OutputFormat::Html; "Test HTML")]
+#[test_case(OutputFormat::Xhtml; "Test XHTML")]
+fn test_extract_file_to_document(format: OutputFormat) {
+    for content in [extract_to_string(format), extract_to_stream(format)] {
+        assert!(content.contains("<head>"));
+        assert!(content.contains("<meta name="));
+        assert!(content.contains("A Heading 1</h1>"));
+    }
+}
+
+#[test]
+fn test_extract_file_to_body_xhtml() {
+    for content in [
+        extract_to_string(OutputFormat::BodyXhtml),
+        extract_to_stream(OutputFormat::BodyXhtml),
+    ] {
+        assert!(!content.contains("<head>"));
+        assert!(!content.contains("<meta"));
+        assert!(!content.contains("<body"));
+        assert!(!content.starts_with("<?xml"));
+        assert!(content.contains("A Heading 1</h1>"));
+    }
+}
+
+#[test]
+fn test_extract_file_to_json() {
+    let (text, metadata) = Extractor::new().extract_file_to_string(FILE_PATH).unwrap();
+
+    for content in [
+        extract_to_string(OutputFormat::Json),
+        extract_to_stream(OutputFormat::Json),
+    ] {
+        let json: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert_eq!(json["content"].as_str().unwrap().trim(), text.trim());
+
+        let json_metadata: Metadata = serde_json::from_value(json["metadata"].clone()).unwrap();
+        assert_eq!(
+            json_metadata.get("Content-Type"),
+            metadata.get("Content-Type")
+        );
+    }
+}
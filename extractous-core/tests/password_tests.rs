@@ -0,0 +1,59 @@
+use extractous::{Error, Extractor};
+use std::io::Read;
+
+const ENCRYPTED_PDF: &str = "../test_files/documents/encrypted.pdf";
+const PASSWORD: &str = "extractous";
+
+#[test]
+fn test_extract_encrypted_without_password() {
+    let result = Extractor::new().extract_file_to_string(ENCRYPTED_PDF);
+    assert!(matches!(result, Err(Error::EncryptedDocument(_))));
+}
+
+#[test]
+fn test_extract_encrypted_with_password() {
+    let extractor = Extractor::new().set_password(PASSWORD);
+
+    let (content, _metadata) = extractor.extract_file_to_string(ENCRYPTED_PDF).unwrap();
+    assert!(content.contains("Confidential contract"));
+
+    let (mut stream, _metadata) = extractor.extract_file(ENCRYPTED_PDF).unwrap();
+    let mut content = String::new();
+    stream.read_to_string(&mut content).unwrap();
+    assert!(content.contains("Confidential contract"));
+}
+
+#[test]
+fn test_extract_encrypted_with_password_candidates() {
+    let extractor = Extractor::new().set_passwords(&["wrong", PASSWORD]);
+    let (content, _metadata) = extractor.extract_file_to_string(ENCRYPTED_PDF).unwrap();
+    assert!(content.contains("Confidential contract"));
+
+    let documents = extractor.extract_file_recursive(ENCRYPTED_PDF).unwrap();
+    assert!(documents[0].content.contains("Confidential contract"));
+
+    let extractor = Extractor::new().set_passwords(&["wrong", "also wrong"]);
+    let result = extractor.extract_file_to_string(ENCRYPTED_PDF);
+    assert!(matches!(result, Err(Error::EncryptedDocument(_))));
+}
+
+#[test]
+fn test_extract_unencrypted_with_password_candidates() {
+    let extractor = Extractor::new().set_passwords(&["wrong", PASSWORD]);
+    let (content, _metadata) = extractor
+        .extract_file_to_string("../test_files/documents/simple.odt")
+        .unwrap();
+    assert!(!content.is_empty());
+}
+
+#[test]
+fn test_extract_unencryptable_type_with_password_candidates() {
+    // Images cannot be encrypted, they are parsed once with the first candidate
+    let file_path = "../test_files/documents/table-multi-row-column-cells.png";
+    let (expected, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+
+    let extractor = Extractor::new().set_passwords(&["wrong", PASSWORD]);
+    let (content, metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, expected);
+    assert_eq!(metadata.get("Content-Type").unwrap()[0], "image/png");
+}
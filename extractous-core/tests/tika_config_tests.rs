@@ -0,0 +1,66 @@
+use extractous::{Error, Extractor};
+
+const PDF: &str = "../test_files/documents/2022_Q3_AAPL.pdf";
+
+const EXCLUDE_PDF_PARSER: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<properties>
+  <parsers>
+    <parser class="org.apache.tika.parser.DefaultParser">
+      <parser-exclude class="org.apache.tika.parser.pdf.PDFParser"/>
+    </parser>
+  </parsers>
+</properties>"#;
+
+#[test]
+fn test_tika_config_xml_excludes_parser() {
+    let (content, _metadata) = Extractor::new().extract_file_to_string(PDF).unwrap();
+    assert!(!content.trim().is_empty());
+
+    // Without a pdf parser, the detected pdf is parsed by the empty fallback parser
+    let extractor = Extractor::new()
+        .set_tika_config_xml(EXCLUDE_PDF_PARSER)
+        .unwrap();
+    let (content, metadata) = extractor.extract_file_to_string(PDF).unwrap();
+    assert!(content.trim().is_empty());
+    assert_eq!(metadata.get("Content-Type").unwrap()[0], "application/pdf");
+
+    // The clones share the loaded config
+    let (content, _metadata) = extractor.clone().extract_file_to_string(PDF).unwrap();
+    assert!(content.trim().is_empty());
+}
+
+#[test]
+fn test_tika_config_file() {
+    // Unique per process, so that concurrent test runs do not share the file
+    let path = std::env::temp_dir().join(format!(
+        "extractous-tika-config-test-{}.xml",
+        std::process::id()
+    ));
+    std::fs::write(&path, EXCLUDE_PDF_PARSER).unwrap();
+
+    let extractor = Extractor::new().set_tika_config_file(&path).unwrap();
+    let (content, _metadata) = extractor.extract_file_to_string(PDF).unwrap();
+    assert!(content.trim().is_empty());
+    std::fs::remove_file(&path).unwrap();
+
+    // A missing file is told apart from an invalid config
+    let result = Extractor::new().set_tika_config_file(&path);
+    match result {
+        Err(Error::FileNotFound(context)) => {
+            assert_eq!(context.path, Some(path.display().to_string()));
+            assert!(context.message.contains("extractous-tika-config"));
+        }
+        Err(e) => panic!("expected a missing file, got {:?}", e),
+        Ok(_) => panic!("expected a missing file"),
+    }
+}
+
+#[test]
+fn test_invalid_tika_config() {
+    let result = Extractor::new().set_tika_config_xml("<properties><parsers>");
+    assert!(matches!(result, Err(Error::InvalidConfig(_))));
+
+    // The root element of a tika config must be properties
+    let result = Extractor::new().set_tika_config_xml("<configuration/>");
+    assert!(matches!(result, Err(Error::InvalidConfig(msg)) if msg.contains("properties")));
+}
@@ -0,0 +1,170 @@
+use extractous::{CancellationToken, Error, Extractor};
+use std::io::{self, Read};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Reader that trickles its bytes one at a time, so the parsing takes far longer than the tests
+struct TrickleReader {
+    bytes: Vec<u8>,
+    pos: usize,
+}
+
+impl TrickleReader {
+    fn new() -> Self {
+        Self {
+            bytes: std::fs::read("../test_files/documents/2022_Q3_AAPL.pdf").unwrap(),
+            pos: 0,
+        }
+    }
+}
+
+impl Read for TrickleReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.pos >= self.bytes.len() || buf.is_empty() {
+            return Ok(0);
+        }
+        thread::sleep(Duration::from_millis(10));
+        buf[0] = self.bytes[self.pos];
+        self.pos += 1;
+        Ok(1)
+    }
+}
+
+fn abort_error(err: &io::Error) -> Option<&Error> {
+    err.get_ref().and_then(|e| e.downcast_ref::<Error>())
+}
+
+#[test]
+fn test_parse_timeout_to_string() {
+    let extractor = Extractor::new().set_parse_timeout(Duration::from_millis(300));
+
+    let start = Instant::now();
+    let result = extractor.extract_reader_to_string(TrickleReader::new());
+    assert!(
+        matches!(result, Err(Error::Timeout(_))),
+        "Expected a timeout, got {:?}",
+        result
+    );
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
+
+#[test]
+fn test_parse_timeout_stream() {
+    let extractor = Extractor::new().set_parse_timeout(Duration::from_millis(300));
+
+    let (mut stream, _metadata) = extractor.extract_reader(TrickleReader::new()).unwrap();
+    let mut buffer = Vec::new();
+    let err = stream.read_to_end(&mut buffer).unwrap_err();
+
+    assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+    assert!(matches!(abort_error(&err), Some(Error::Timeout(_))));
+}
+
+#[test]
+fn test_parse_timeout_context() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new().set_parse_timeout(Duration::from_millis(1));
+
+    let err = extractor.extract_file_to_string(file_path).unwrap_err();
+    let Error::Timeout(context) = &err else {
+        panic!("Expected a timeout, got {:?}", err);
+    };
+    assert_eq!(err.context(), Some(context));
+    assert!(context.message.contains("timed out"));
+    assert_eq!(context.path.as_deref(), Some(file_path));
+    assert!(context
+        .java_class
+        .as_ref()
+        .is_some_and(|class| class.ends_with("AbortedException")));
+    assert!(context.stack_trace.is_some());
+
+    // Streams report the same details
+    let (mut stream, _metadata) = Extractor::new()
+        .set_parse_timeout(Duration::from_millis(300))
+        .extract_reader(TrickleReader::new())
+        .unwrap();
+    let err = stream.read_to_end(&mut Vec::new()).unwrap_err();
+    match abort_error(&err) {
+        Some(Error::Timeout(context)) => {
+            assert!(context.java_class.is_some());
+            assert!(context.path.is_none());
+        }
+        other => panic!("Expected a timeout, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_parse_timeout_not_reached() {
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let (expected, _metadata) = Extractor::new().extract_file_to_string(file_path).unwrap();
+
+    let extractor = Extractor::new().set_parse_timeout(Duration::from_secs(120));
+    let (content, _metadata) = extractor.extract_file_to_string(file_path).unwrap();
+    assert_eq!(content, expected);
+}
+
+#[test]
+fn test_cancel_to_string() {
+    let token = CancellationToken::new();
+    let extractor = Extractor::new().set_cancellation_token(token.clone());
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        token.cancel();
+    });
+    let result = extractor.extract_reader_to_string(TrickleReader::new());
+    canceller.join().unwrap();
+
+    let err = result.unwrap_err();
+    let Error::Cancelled(context) = &err else {
+        panic!("Expected a cancellation, got {:?}", err);
+    };
+    assert_eq!(err.context(), Some(context));
+    assert!(context.message.contains("cancelled"));
+    assert!(context.path.is_none());
+    assert!(context
+        .java_class
+        .as_ref()
+        .is_some_and(|class| class.ends_with("AbortedException")));
+    assert!(context.stack_trace.is_some());
+}
+
+#[test]
+fn test_cancel_stream() {
+    let token = CancellationToken::new();
+    let extractor = Extractor::new().set_cancellation_token(token.clone());
+
+    let canceller = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(300));
+        token.cancel();
+    });
+    let (mut stream, _metadata) = extractor.extract_reader(TrickleReader::new()).unwrap();
+    let mut buffer = Vec::new();
+    let err = stream.read_to_end(&mut buffer).unwrap_err();
+    canceller.join().unwrap();
+
+    match abort_error(&err) {
+        Some(Error::Cancelled(context)) => {
+            assert!(context.java_class.is_some());
+            assert!(context.path.is_none());
+        }
+        other => panic!("Expected a cancellation, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_cancelled_token_fails_right_away() {
+    let token = CancellationToken::new();
+    token.cancel();
+    assert!(token.is_cancelled());
+
+    let file_path = "../test_files/documents/2022_Q3_AAPL.pdf";
+    let extractor = Extractor::new().set_cancellation_token(token);
+    match extractor.extract_file_to_string(file_path) {
+        Err(Error::Cancelled(context)) => {
+            assert_eq!(context.path.as_deref(), Some(file_path));
+            assert!(context.java_class.is_some());
+        }
+        other => panic!("Expected a cancellation, got {:?}", other),
+    }
+}
@@ -4,6 +4,11 @@ import java.io.IOException;
 import java.io.InputStream;
 import java.nio.ByteBuffer;
 
+/**
+ * InputStream over a ByteBuffer, usually a direct buffer over memory owned by Rust.
+ * Reads and close are synchronized, so once close returns the buffer is never read again, even
+ * by a parsing thread that was abandoned after a timeout.
+ */
 public class ByteBufferInputStream extends InputStream {
 
     private ByteBuffer bb;
@@ -13,7 +18,7 @@ public class ByteBufferInputStream extends InputStream {
     }
 
     @Override
-    public int read() throws IOException {
+    public synchronized int read() throws IOException {
         if (bb == null) {
             throw new IOException("read on a closed InputStream");
         }
@@ -26,7 +31,7 @@ public class ByteBufferInputStream extends InputStream {
     }
 
     @Override
-    public int read(byte[] b, int off, int len) throws IOException {
+    public synchronized int read(byte[] b, int off, int len) throws IOException {
 
         if (bb == null) {
             throw new IOException("read on a closed InputStream");
@@ -50,7 +55,7 @@ public class ByteBufferInputStream extends InputStream {
     }
 
     @Override
-    public long skip(long n) throws IOException {
+    public synchronized long skip(long n) throws IOException {
 
         if (bb == null) {
             throw new IOException("skip on a closed InputStream");
@@ -73,7 +78,7 @@ public class ByteBufferInputStream extends InputStream {
     }
 
     @Override
-    public int available() throws IOException {
+    public synchronized int available() throws IOException {
 
         if (bb == null) {
             throw new IOException("available on a closed InputStream");
@@ -83,7 +88,7 @@ public class ByteBufferInputStream extends InputStream {
     }
 
     @Override
-    public void close() throws IOException {
+    public synchronized void close() throws IOException {
         bb = null;
     }
 
@@ -0,0 +1,105 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+public class DetectResult {
+
+    /**
+     * Which detection step decided the returned mime type
+     */
+    public enum Source {
+        MAGIC,
+        FILENAME,
+        CONTAINER,
+        UNKNOWN
+    }
+
+    private final String mimeType;
+    private final Source source;
+    private final byte status;
+    private final String errorMessage;
+    private final ErrorInfo error;
+    private final Metadata metadata;
+
+    public DetectResult(String mimeType, Source source, Metadata metadata) {
+        this.mimeType = mimeType;
+        this.source = source;
+        this.status = 0;
+        this.errorMessage = null;
+        this.error = null;
+        this.metadata = metadata;
+    }
+
+    public DetectResult(byte status, String errorMessage) {
+        this(new ErrorInfo(status, errorMessage));
+    }
+
+    public DetectResult(ErrorInfo error) {
+        this.mimeType = null;
+        this.source = null;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
+        this.metadata = null;
+    }
+
+    /**
+     * Returns the detected mime type or null if there is an error
+     * @return String mime type
+     */
+    public String getMimeType() {
+        return mimeType;
+    }
+
+    /**
+     * Returns the name of the detection source or null if there is an error
+     * @return String one of MAGIC, FILENAME, CONTAINER or UNKNOWN
+     */
+    public String getSource() {
+        return source == null ? null : source.name();
+    }
+
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the tika metadata collected during detection or null if there is an error
+     * @return tika metadata
+     */
+    public Metadata getMetadata() {
+        return metadata;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * otherwise the status of the error, see ErrorInfo.getStatus
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage + " mimeType: " + this.mimeType
+                + " source: " + this.source;
+    }
+}
@@ -0,0 +1,224 @@
+package ai.yobix;
+
+import org.apache.tika.exception.EncryptedDocumentException;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.exception.UnsupportedFormatException;
+import org.apache.tika.exception.WriteLimitReachedException;
+import org.apache.tika.exception.ZeroByteFileException;
+import org.apache.tika.metadata.Metadata;
+
+import javax.net.ssl.SSLException;
+import java.io.FileNotFoundException;
+import java.io.IOException;
+import java.io.PrintWriter;
+import java.io.StringWriter;
+import java.net.MalformedURLException;
+import java.net.SocketException;
+import java.net.SocketTimeoutException;
+import java.net.URISyntaxException;
+import java.net.UnknownHostException;
+import java.nio.file.AccessDeniedException;
+import java.nio.file.NoSuchFileException;
+
+/**
+ * Describes the error of a call: its status, the exception that caused it and what was being
+ * extracted. Every entry point builds its errors with {@link #of(Throwable, String, Metadata)},
+ * so the same exception gets the same status whatever the entry point.
+ */
+public class ErrorInfo {
+
+    public static final byte IO = 1;
+    public static final byte PARSE = 2;
+    public static final byte INVALID_URL = 3;
+    // 4 and 5 are the ParseMonitor TIMED_OUT and CANCELLED
+    public static final byte UNSUPPORTED_ENCODING = 6;
+    public static final byte FILE_NOT_FOUND = 7;
+    public static final byte PERMISSION_DENIED = 8;
+    public static final byte UNSUPPORTED_FORMAT = 9;
+    public static final byte ENCRYPTED_DOCUMENT = 10;
+    public static final byte CORRUPT_DOCUMENT = 11;
+    public static final byte ZERO_BYTE_FILE = 12;
+    public static final byte WRITE_LIMIT_REACHED = 13;
+    public static final byte OCR_FAILURE = 14;
+    public static final byte NETWORK = 15;
+    public static final byte INVALID_CONFIG = 16;
+    public static final byte REJECTED = 17;
+
+    private static final String OCR_PACKAGE = "org.apache.tika.parser.ocr.";
+
+    private final byte status;
+    private final String message;
+    private final String exceptionClass;
+    private final String stackTrace;
+    private final String path;
+    private final String mimeType;
+
+    public ErrorInfo(byte status, String message) {
+        this(status, message, null, null, null, null);
+    }
+
+    private ErrorInfo(byte status, String message, String exceptionClass, String stackTrace,
+                      String path, String mimeType) {
+        this.status = status;
+        this.message = message;
+        this.exceptionClass = exceptionClass;
+        this.stackTrace = stackTrace;
+        this.path = path;
+        this.mimeType = mimeType;
+    }
+
+    /**
+     * Classifies the given exception
+     * @param t the exception thrown by the call
+     * @param path the path of the file or the url, null for bytes and streams
+     * @param metadata the metadata of the document, gives the mime type when it was detected
+     * @return ErrorInfo
+     */
+    public static ErrorInfo of(Throwable t, String path, Metadata metadata) {
+        final String mimeType = metadata == null ? null : metadata.get(Metadata.CONTENT_TYPE);
+        return of(status(t), t, path, mimeType);
+    }
+
+    /**
+     * Describes the given exception with a status decided by the caller
+     * @param status the status of the error
+     * @param t the exception thrown by the call
+     * @param path the path of the file or the url, null if there is none
+     * @param mimeType the mime type of the document, null if unknown
+     * @return ErrorInfo
+     */
+    public static ErrorInfo of(byte status, Throwable t, String path, String mimeType) {
+        final StringWriter stackTrace = new StringWriter();
+        t.printStackTrace(new PrintWriter(stackTrace));
+
+        String message = t.getMessage();
+        if (message == null || message.isEmpty()) {
+            message = t.getClass().getName();
+        }
+
+        return new ErrorInfo(status, message, t.getClass().getName(), stackTrace.toString(),
+                path, mimeType);
+    }
+
+    /**
+     * Returns the status of the most specific exception of the cause chain
+     */
+    private static byte status(Throwable t) {
+        final ParseMonitor.AbortedException aborted = find(t, ParseMonitor.AbortedException.class);
+        if (aborted != null) {
+            return aborted.getStatus();
+        }
+        if (find(t, RejectedDocumentException.class) != null) {
+            return REJECTED;
+        }
+        if (find(t, ZeroByteFileException.class) != null) {
+            return ZERO_BYTE_FILE;
+        }
+        if (find(t, EncryptedDocumentException.class) != null) {
+            return ENCRYPTED_DOCUMENT;
+        }
+        if (find(t, UnsupportedFormatException.class) != null) {
+            return UNSUPPORTED_FORMAT;
+        }
+        if (find(t, WriteLimitReachedException.class) != null) {
+            return WRITE_LIMIT_REACHED;
+        }
+        if (find(t, NoSuchFileException.class) != null || find(t, FileNotFoundException.class) != null) {
+            return FILE_NOT_FOUND;
+        }
+        if (find(t, AccessDeniedException.class) != null || find(t, SecurityException.class) != null) {
+            return PERMISSION_DENIED;
+        }
+        if (find(t, MalformedURLException.class) != null || find(t, URISyntaxException.class) != null) {
+            return INVALID_URL;
+        }
+        if (find(t, UnknownHostException.class) != null || find(t, SocketException.class) != null
+                || find(t, SocketTimeoutException.class) != null || find(t, SSLException.class) != null) {
+            return NETWORK;
+        }
+        if (isOcrFailure(t)) {
+            return OCR_FAILURE;
+        }
+        if (t instanceof TikaException) {
+            return CORRUPT_DOCUMENT;
+        }
+        return t instanceof IOException ? IO : PARSE;
+    }
+
+    private static <T extends Throwable> T find(Throwable t, Class<T> type) {
+        for (Throwable cause = t; cause != null; cause = cause.getCause()) {
+            if (type.isInstance(cause)) {
+                return type.cast(cause);
+            }
+            if (cause.getCause() == cause) {
+                break;
+            }
+        }
+        return null;
+    }
+
+    /**
+     * The OCR parser fails with generic exceptions, tells them apart by where they were thrown
+     */
+    private static boolean isOcrFailure(Throwable t) {
+        for (Throwable cause = t; cause != null; cause = cause.getCause()) {
+            for (StackTraceElement element : cause.getStackTrace()) {
+                if (element.getClassName().startsWith(OCR_PACKAGE)) {
+                    return true;
+                }
+            }
+            if (cause.getCause() == cause) {
+                break;
+            }
+        }
+        return false;
+    }
+
+    /**
+     * Returns the status of the error
+     * @return
+     * 1: IO, 2: PARSE, 3: INVALID_URL, 4: TIMED_OUT, 5: CANCELLED, 6: UNSUPPORTED_ENCODING,
+     * 7: FILE_NOT_FOUND, 8: PERMISSION_DENIED, 9: UNSUPPORTED_FORMAT, 10: ENCRYPTED_DOCUMENT,
+     * 11: CORRUPT_DOCUMENT, 12: ZERO_BYTE_FILE, 13: WRITE_LIMIT_REACHED, 14: OCR_FAILURE,
+     * 15: NETWORK, 16: INVALID_CONFIG, 17: REJECTED
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    public String getMessage() {
+        return message;
+    }
+
+    /**
+     * Returns the class name of the exception, or null if the error has no exception
+     */
+    public String getExceptionClass() {
+        return exceptionClass;
+    }
+
+    /**
+     * Returns the stack trace of the exception, or null if the error has no exception
+     */
+    public String getStackTrace() {
+        return stackTrace;
+    }
+
+    /**
+     * Returns the path of the file or the url, or null for bytes and streams
+     */
+    public String getPath() {
+        return path;
+    }
+
+    /**
+     * Returns the mime type of the document, or null if it was not detected before the error
+     */
+    public String getMimeType() {
+        return mimeType;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.message + " exception: " + this.exceptionClass;
+    }
+}
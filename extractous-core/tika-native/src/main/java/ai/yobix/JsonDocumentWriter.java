@@ -0,0 +1,113 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+import java.io.IOException;
+import java.io.StringWriter;
+import java.io.Writer;
+
+/**
+ * Writes the content of a document and its metadata as one JSON document:
+ * {"content":"...","metadata":{"name":["value",...],...}}
+ * The content is escaped while it is written, so it can be streamed before the metadata is complete
+ */
+public class JsonDocumentWriter extends Writer {
+
+    private final Writer out;
+
+    public JsonDocumentWriter(Writer out) throws IOException {
+        this.out = out;
+        out.write("{\"content\":\"");
+    }
+
+    /**
+     * Returns the JSON document of the given content and metadata
+     */
+    public static String toJson(String content, Metadata metadata) {
+        final StringWriter writer = new StringWriter();
+        try (final JsonDocumentWriter json = new JsonDocumentWriter(writer)) {
+            json.write(content);
+            json.finish(metadata);
+        } catch (IOException e) {
+            // StringWriter does not throw
+            throw new IllegalStateException(e);
+        }
+        return writer.toString();
+    }
+
+    @Override
+    public void write(char[] cbuf, int off, int len) throws IOException {
+        for (int i = off; i < off + len; i++) {
+            writeEscaped(cbuf[i]);
+        }
+    }
+
+    /**
+     * Ends the content and writes the metadata, which completes the document
+     */
+    public void finish(Metadata metadata) throws IOException {
+        out.write("\",\"metadata\":{");
+        final String[] names = metadata.names();
+        for (int i = 0; i < names.length; i++) {
+            if (i > 0) {
+                out.write(',');
+            }
+            writeString(names[i]);
+            out.write(":[");
+            final String[] values = metadata.getValues(names[i]);
+            for (int j = 0; j < values.length; j++) {
+                if (j > 0) {
+                    out.write(',');
+                }
+                writeString(values[j]);
+            }
+            out.write(']');
+        }
+        out.write("}}");
+        out.flush();
+    }
+
+    private void writeString(String value) throws IOException {
+        out.write('"');
+        for (int i = 0; i < value.length(); i++) {
+            writeEscaped(value.charAt(i));
+        }
+        out.write('"');
+    }
+
+    private void writeEscaped(char c) throws IOException {
+        switch (c) {
+            case '"':
+                out.write("\\\"");
+                break;
+            case '\\':
+                out.write("\\\\");
+                break;
+            case '\n':
+                out.write("\\n");
+                break;
+            case '\r':
+                out.write("\\r");
+                break;
+            case '\t':
+                out.write("\\t");
+                break;
+            default:
+                if (c < 0x20) {
+                    out.write(String.format("\\u%04x", (int) c));
+                } else {
+                    out.write(c);
+                }
+        }
+    }
+
+    @Override
+    public void flush() throws IOException {
+        out.flush();
+    }
+
+    @Override
+    public void close() throws IOException {
+        out.close();
+    }
+}
@@ -0,0 +1,84 @@
+package ai.yobix;
+
+public class LanguageDetectionResult {
+
+    private final String[] languages;
+    private final float[] confidences;
+    private final byte status;
+    private final String errorMessage;
+    private final ErrorInfo error;
+
+    public LanguageDetectionResult(String[] languages, float[] confidences) {
+        this.languages = languages;
+        this.confidences = confidences;
+        this.status = 0;
+        this.errorMessage = null;
+        this.error = null;
+    }
+
+    public LanguageDetectionResult(byte status, String errorMessage) {
+        this(new ErrorInfo(status, errorMessage));
+    }
+
+    public LanguageDetectionResult(ErrorInfo error) {
+        this.languages = null;
+        this.confidences = null;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
+    }
+
+    /**
+     * Returns the ISO 639-1 codes of the detected languages, most probable first, or null if there
+     * is an error
+     * @return String[] language codes
+     */
+    public String[] getLanguages() {
+        return languages;
+    }
+
+    /**
+     * Returns the probability of each detected language, between 0 and 1, or null if there is an
+     * error
+     * @return float[] probabilities
+     */
+    public float[] getConfidences() {
+        return confidences;
+    }
+
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 1: IOException, the language models could not be loaded
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage + " languages: "
+                + String.join(",", this.languages == null ? new String[0] : this.languages);
+    }
+}
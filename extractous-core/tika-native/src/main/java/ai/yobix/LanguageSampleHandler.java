@@ -0,0 +1,82 @@
+package ai.yobix;
+
+import org.apache.tika.language.detect.LanguageResult;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.util.List;
+
+/**
+ * Passes the content to the output handler and keeps a sample of the text of the body, which the
+ * languages are detected from. The languages are detected on the text whatever the output format,
+ * so the markup of XHTML, HTML or JSON outputs is not mistaken for words
+ */
+public class LanguageSampleHandler extends ContentHandlerDecorator {
+
+    /** The languages are detected from the first characters of the text only */
+    static final int MAX_SAMPLE_LENGTH = 100_000;
+
+    // The same keys as Tika's own language detection, read by the Rust DocumentMetadata
+    static final String DETECTED_LANGUAGE = "X-TIKA:detected_language";
+    static final String DETECTED_LANGUAGE_CONFIDENCE = "X-TIKA:detected_language_confidence_raw";
+
+    private final StringBuilder sample = new StringBuilder();
+    private int bodyDepth = 0;
+
+    public LanguageSampleHandler(ContentHandler handler) {
+        super(handler);
+    }
+
+    @Override
+    public void startElement(String uri, String localName, String name, Attributes atts) throws SAXException {
+        if ("body".equals(localName)) {
+            bodyDepth++;
+        }
+        super.startElement(uri, localName, name, atts);
+    }
+
+    @Override
+    public void endElement(String uri, String localName, String name) throws SAXException {
+        if ("body".equals(localName)) {
+            bodyDepth--;
+        }
+        super.endElement(uri, localName, name);
+    }
+
+    @Override
+    public void characters(char[] ch, int start, int length) throws SAXException {
+        sample(ch, start, length);
+        super.characters(ch, start, length);
+    }
+
+    @Override
+    public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+        // Parsers separate the paragraphs with ignorable new lines
+        sample(ch, start, length);
+        super.ignorableWhitespace(ch, start, length);
+    }
+
+    private void sample(char[] ch, int start, int length) {
+        if (bodyDepth > 0 && sample.length() < MAX_SAMPLE_LENGTH) {
+            sample.append(ch, start, Math.min(length, MAX_SAMPLE_LENGTH - sample.length()));
+        }
+    }
+
+    /**
+     * Detects the languages of the sampled text and adds them to the metadata, most probable
+     * first, with their confidence
+     */
+    public void addLanguages(Metadata metadata) throws IOException {
+        final List<LanguageResult> languages = TikaNativeMain.detectKnownLanguages(sample.toString());
+        metadata.remove(DETECTED_LANGUAGE);
+        metadata.remove(DETECTED_LANGUAGE_CONFIDENCE);
+        for (LanguageResult language : languages) {
+            metadata.add(DETECTED_LANGUAGE, language.getLanguage());
+            metadata.add(DETECTED_LANGUAGE_CONFIDENCE, Float.toString(language.getRawScore()));
+        }
+    }
+}
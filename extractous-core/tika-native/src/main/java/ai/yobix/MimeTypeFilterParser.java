@@ -0,0 +1,76 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.util.Locale;
+
+/**
+ * Decorates a parser to only parse the documents whose mime type is accepted. Set as the parser
+ * of the ParseContext, it also filters the embedded documents.
+ * The type is detected from the start of the document, then rejected documents fail with a
+ * RejectedDocumentException without being given to the parser. The embedded documents that fail
+ * are skipped and the exception is recorded in the metadata, as for any embedded document that
+ * cannot be parsed.
+ * A pattern is either a mime type, for example application/pdf, or a top level type followed by
+ * a wildcard, for example image/*. The denied types take precedence over the allowed ones, and
+ * an empty allow list allows every type.
+ */
+public class MimeTypeFilterParser extends ParserDecorator {
+
+    private final Detector detector;
+    private final String[] allowed;
+    private final String[] denied;
+
+    public MimeTypeFilterParser(Parser parser, Detector detector, String[] allowed, String[] denied) {
+        super(parser);
+        this.detector = detector;
+        this.allowed = allowed;
+        this.denied = denied;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (allowed.length == 0 && denied.length == 0) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        try (final TemporaryResources tmp = new TemporaryResources()) {
+            // The detectors reset the stream, so the parser still gets the whole document
+            final TikaInputStream tikaStream = TikaInputStream.get(stream, tmp, metadata);
+            final MediaType type = detector.detect(tikaStream, metadata);
+
+            if (matches(type, denied) || (allowed.length > 0 && !matches(type, allowed))) {
+                metadata.set(Metadata.CONTENT_TYPE, type.toString());
+                throw new RejectedDocumentException(type.getBaseType().toString());
+            }
+            super.parse(tikaStream, handler, metadata, context);
+        }
+    }
+
+    private static boolean matches(MediaType type, String[] patterns) {
+        final String baseType = type.getBaseType().toString();
+        for (String pattern : patterns) {
+            final String normalized = pattern.trim().toLowerCase(Locale.ROOT);
+            if (normalized.endsWith("/*")
+                    ? type.getType().equals(normalized.substring(0, normalized.length() - 2))
+                    : baseType.equals(normalized)) {
+                return true;
+            }
+        }
+        return false;
+    }
+}
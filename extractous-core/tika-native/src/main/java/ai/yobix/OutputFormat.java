@@ -0,0 +1,84 @@
+package ai.yobix;
+
+import org.apache.tika.sax.BasicContentHandlerFactory;
+import org.apache.tika.sax.BodyContentHandler;
+import org.apache.tika.sax.ToHTMLContentHandler;
+import org.apache.tika.sax.ToTextContentHandler;
+import org.apache.tika.sax.ToXMLContentHandler;
+import org.apache.tika.sax.WriteOutContentHandler;
+import org.xml.sax.ContentHandler;
+
+import java.io.OutputStream;
+import java.io.UnsupportedEncodingException;
+
+/**
+ * Format of the parsed content. Selects the content handler receiving the SAX events of the parsers.
+ * The names match the Rust OutputFormat enum, which passes them as strings
+ */
+public enum OutputFormat {
+    /** The text of the body */
+    PLAIN_TEXT,
+    /** The whole XHTML document, with the metadata in its head */
+    XHTML,
+    /** The whole document as HTML */
+    HTML,
+    /** The elements inside the body of the XHTML document, without head and meta elements */
+    BODY_XHTML,
+    /** The text of the body and the metadata as one JSON document, see JsonDocumentWriter */
+    JSON;
+
+    /**
+     * Returns a handler writing up to writeLimit characters of content to a string, available
+     * with toString(). For the formats keeping the body only, the limit applies to the body
+     */
+    public ContentHandler newHandler(int writeLimit) {
+        switch (this) {
+            case XHTML:
+                return new WriteOutContentHandler(new ToXMLContentHandler(), writeLimit);
+            case HTML:
+                return new WriteOutContentHandler(new ToHTMLContentHandler(), writeLimit);
+            case BODY_XHTML:
+                return new BodyContentHandler(new WriteOutContentHandler(new ToXMLContentHandler(), writeLimit));
+            default:
+                return new BodyContentHandler(new WriteOutContentHandler(writeLimit));
+        }
+    }
+
+    /**
+     * Returns a handler writing the content to the given stream. JSON needs the metadata once the
+     * parsing is done, use JsonDocumentWriter instead
+     */
+    public ContentHandler newHandler(OutputStream stream, String encoding) throws UnsupportedEncodingException {
+        switch (this) {
+            case XHTML:
+                return new ToXMLContentHandler(stream, encoding);
+            case HTML:
+                return new ToHTMLContentHandler(stream, encoding);
+            case BODY_XHTML:
+                // The xml declaration belongs to a full document only
+                return new BodyContentHandler(new ToXMLContentHandler(stream, encoding) {
+                    @Override
+                    public void startDocument() {
+                    }
+                });
+            default:
+                return new BodyContentHandler(new ToTextContentHandler(stream, encoding));
+        }
+    }
+
+    /**
+     * Returns the handler type of the documents parsed recursively. Each document comes with its
+     * own metadata, so JSON documents have the text only
+     */
+    public BasicContentHandlerFactory.HANDLER_TYPE recursiveHandlerType() {
+        switch (this) {
+            case XHTML:
+            case BODY_XHTML:
+                return BasicContentHandlerFactory.HANDLER_TYPE.XML;
+            case HTML:
+                return BasicContentHandlerFactory.HANDLER_TYPE.HTML;
+            default:
+                return BasicContentHandlerFactory.HANDLER_TYPE.TEXT;
+        }
+    }
+}
@@ -0,0 +1,214 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.sax.ContentHandlerDecorator;
+import org.xml.sax.Attributes;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+
+import java.io.IOException;
+import java.util.concurrent.CancellationException;
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.Executors;
+import java.util.concurrent.FutureTask;
+import java.util.concurrent.ScheduledExecutorService;
+import java.util.concurrent.ScheduledFuture;
+import java.util.concurrent.TimeUnit;
+
+/**
+ * Enforces the timeout and the cancellation of a single extraction.
+ * The monitor is started with an abort action that stops the parsing. The action runs once,
+ * either when the timeout expires or when the monitor is cancelled, unless parsing finished first.
+ */
+public class ParseMonitor {
+
+    public static final byte RUNNING = 0;
+    public static final byte TIMED_OUT = 4;
+    public static final byte CANCELLED = 5;
+
+    private static final ScheduledExecutorService timer = Executors.newSingleThreadScheduledExecutor(command -> {
+        Thread thread = new Thread(command, "Apache Tika timeout");
+        thread.setDaemon(true);
+        return thread;
+    });
+
+    private final long timeoutMillis;
+    private volatile byte status = RUNNING;
+    private boolean finished = false;
+    private Runnable abortAction;
+    private ScheduledFuture<?> timeoutTask;
+
+    /**
+     * @param timeoutMillis the maximum duration of the parsing in milliseconds, 0 for no timeout
+     */
+    public ParseMonitor(long timeoutMillis) {
+        this.timeoutMillis = timeoutMillis;
+    }
+
+    /**
+     * Aborts the parsing. Can be called from any thread, and before the parsing starts
+     */
+    public void cancel() {
+        abort(CANCELLED);
+    }
+
+    /**
+     * Returns the status of the monitor
+     * @return
+     * 0: RUNNING, the parsing was not aborted
+     * 4: TIMED_OUT
+     * 5: CANCELLED
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    public boolean isAborted() {
+        return status != RUNNING;
+    }
+
+    /**
+     * Returns the reason of the abort or null if the parsing was not aborted
+     * @return String representing the reason of the abort
+     */
+    public String getMessage() {
+        switch (status) {
+            case TIMED_OUT:
+                return "Parsing timed out after " + timeoutMillis + " ms";
+            case CANCELLED:
+                return "Parsing was cancelled";
+            default:
+                return null;
+        }
+    }
+
+    synchronized void start(Runnable abortAction) {
+        this.abortAction = abortAction;
+        if (status != RUNNING) {
+            // Cancelled before the parsing even started
+            abortAction.run();
+        } else if (timeoutMillis > 0) {
+            timeoutTask = timer.schedule(() -> abort(TIMED_OUT), timeoutMillis, TimeUnit.MILLISECONDS);
+        }
+    }
+
+    synchronized void finish() {
+        finished = true;
+        if (timeoutTask != null) {
+            timeoutTask.cancel(false);
+        }
+    }
+
+    private synchronized void abort(byte reason) {
+        if (finished || status != RUNNING) {
+            return;
+        }
+        status = reason;
+        if (abortAction != null) {
+            abortAction.run();
+        }
+    }
+
+    /**
+     * Wraps the handler so that parsers stop at their next output once the monitor is aborted,
+     * even if they ignore thread interrupts
+     */
+    ContentHandler wrap(ContentHandler handler) {
+        return new ContentHandlerDecorator(handler) {
+            @Override
+            public void startElement(String uri, String localName, String name, Attributes atts)
+                    throws SAXException {
+                check();
+                super.startElement(uri, localName, name, atts);
+            }
+
+            @Override
+            public void characters(char[] ch, int start, int length) throws SAXException {
+                check();
+                super.characters(ch, start, length);
+            }
+
+            @Override
+            public void ignorableWhitespace(char[] ch, int start, int length) throws SAXException {
+                check();
+                super.ignorableWhitespace(ch, start, length);
+            }
+        };
+    }
+
+    private void check() throws SAXException {
+        if (isAborted()) {
+            throw new SAXException(getMessage());
+        }
+    }
+
+    /**
+     * Runs the parse task on its own thread and waits for it. When the monitor is aborted the
+     * wait ends right away with an AbortedException, and the parsing thread is interrupted and
+     * left to stop on its own. Callers must close the parsed stream, so that the abandoned
+     * thread can't read from it anymore.
+     */
+    void run(ParseTask task) throws IOException, SAXException, TikaException {
+        final FutureTask<Void> future = new FutureTask<>(() -> {
+            task.run();
+            return null;
+        });
+        final Thread thread = new Thread(future, "Apache Tika");
+        thread.setDaemon(true);
+
+        start(() -> future.cancel(true));
+        thread.start();
+
+        try {
+            future.get();
+        } catch (CancellationException e) {
+            throw new AbortedException(this);
+        } catch (InterruptedException e) {
+            future.cancel(true);
+            Thread.currentThread().interrupt();
+            throw new IOException("Interrupted while waiting for the parsing", e);
+        } catch (ExecutionException e) {
+            if (isAborted()) {
+                throw new AbortedException(this);
+            }
+            final Throwable cause = e.getCause();
+            if (cause instanceof IOException) {
+                throw (IOException) cause;
+            } else if (cause instanceof SAXException) {
+                throw (SAXException) cause;
+            } else if (cause instanceof TikaException) {
+                throw (TikaException) cause;
+            } else if (cause instanceof RuntimeException) {
+                throw (RuntimeException) cause;
+            } else if (cause instanceof Error) {
+                throw (Error) cause;
+            }
+            throw new TikaException("Unexpected parsing failure", cause);
+        } finally {
+            finish();
+        }
+    }
+
+    interface ParseTask {
+        void run() throws IOException, SAXException, TikaException;
+    }
+
+    /**
+     * Thrown when the parsing is aborted by a timeout or a cancellation
+     */
+    public static class AbortedException extends TikaException {
+        private final byte status;
+
+        AbortedException(ParseMonitor monitor) {
+            super(monitor.getMessage());
+            this.status = monitor.getStatus();
+        }
+
+        /**
+         * Returns the status of the monitor that aborted the parsing, see ParseMonitor.getStatus
+         */
+        public byte getStatus() {
+            return status;
+        }
+    }
+}
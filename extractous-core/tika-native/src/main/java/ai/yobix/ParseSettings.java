@@ -0,0 +1,145 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.microsoft.OfficeParserConfig;
+import org.apache.tika.parser.ocr.TesseractOCRConfig;
+import org.apache.tika.parser.pdf.PDFParserConfig;
+
+/**
+ * Settings of one extraction, shared by all the parse calls of TikaNativeMain. Built by the Rust
+ * side with the setters, the same way as the parser configs. Settings that are not set keep
+ * the defaults of the Rust Extractor
+ */
+public class ParseSettings {
+
+    private String charsetName = "UTF-8";
+    private int maxLength = -1;
+    private PDFParserConfig pdfConfig = new PDFParserConfig();
+    private OfficeParserConfig officeConfig = new OfficeParserConfig();
+    private TesseractOCRConfig tesseractConfig = new TesseractOCRConfig();
+    private String[] passwords = new String[0];
+    private TikaConfig tikaConfig;
+    private String[] allowedTypes = new String[0];
+    private String[] deniedTypes = new String[0];
+    private OutputFormat outputFormat = OutputFormat.PLAIN_TEXT;
+    private boolean detectLanguages = false;
+    private ParseMonitor monitor;
+
+    public ParseSettings() {
+    }
+
+    /**
+     * @param charsetName the encoding of the streams
+     */
+    public void setCharsetName(String charsetName) {
+        this.charsetName = charsetName;
+    }
+
+    /**
+     * @param maxLength maximum length of the strings, -1 for no limit
+     */
+    public void setMaxLength(int maxLength) {
+        this.maxLength = maxLength;
+    }
+
+    public void setPdfConfig(PDFParserConfig pdfConfig) {
+        this.pdfConfig = pdfConfig;
+    }
+
+    public void setOfficeConfig(OfficeParserConfig officeConfig) {
+        this.officeConfig = officeConfig;
+    }
+
+    public void setTesseractConfig(TesseractOCRConfig tesseractConfig) {
+        this.tesseractConfig = tesseractConfig;
+    }
+
+    /**
+     * @param passwords the candidate passwords of the encrypted documents, tried in order
+     */
+    public void setPasswords(String[] passwords) {
+        this.passwords = passwords;
+    }
+
+    /**
+     * @param tikaConfig the config loaded by loadTikaConfig, null for the default config
+     */
+    public void setTikaConfig(TikaConfig tikaConfig) {
+        this.tikaConfig = tikaConfig;
+    }
+
+    public void setAllowedTypes(String[] allowedTypes) {
+        this.allowedTypes = allowedTypes;
+    }
+
+    public void setDeniedTypes(String[] deniedTypes) {
+        this.deniedTypes = deniedTypes;
+    }
+
+    /**
+     * @param outputFormat the name of an OutputFormat constant
+     */
+    public void setOutputFormat(String outputFormat) {
+        this.outputFormat = OutputFormat.valueOf(outputFormat);
+    }
+
+    /**
+     * @param detectLanguages whether to detect the languages of the string results, and of each
+     *                        document of the recursive results
+     */
+    public void setDetectLanguages(boolean detectLanguages) {
+        this.detectLanguages = detectLanguages;
+    }
+
+    /**
+     * @param monitor enforces the timeout and the cancellation of the parsing, can be null
+     */
+    public void setMonitor(ParseMonitor monitor) {
+        this.monitor = monitor;
+    }
+
+    public String getCharsetName() {
+        return charsetName;
+    }
+
+    public int getMaxLength() {
+        return maxLength;
+    }
+
+    public OutputFormat getOutputFormat() {
+        return outputFormat;
+    }
+
+    public boolean isDetectLanguages() {
+        return detectLanguages;
+    }
+
+    public ParseMonitor getMonitor() {
+        return monitor;
+    }
+
+    /**
+     * Builds the parser of an extraction. The mime type filter comes first, so the rejected
+     * documents are not even probed with the passwords
+     */
+    public Parser newParser() {
+        final TikaConfig config = tikaConfig != null ? tikaConfig : TikaConfig.getDefaultConfig();
+        return new MimeTypeFilterParser(
+                new PasswordParser(new AutoDetectParser(config), config.getDetector(), passwords, monitor),
+                config.getDetector(), allowedTypes, deniedTypes);
+    }
+
+    /**
+     * Returns a parse context holding the parser configs
+     */
+    public ParseContext newParseContext() {
+        final ParseContext context = new ParseContext();
+        context.set(PDFParserConfig.class, pdfConfig);
+        context.set(OfficeParserConfig.class, officeConfig);
+        context.set(TesseractOCRConfig.class, tesseractConfig);
+        return context;
+    }
+}
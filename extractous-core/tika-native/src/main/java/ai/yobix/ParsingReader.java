@@ -1,16 +1,13 @@
 package ai.yobix;
 
 import java.io.*;
-import java.util.concurrent.Executor;
 
 import org.apache.tika.parser.ParseContext;
 import org.apache.tika.parser.Parser;
 import org.xml.sax.ContentHandler;
-import org.apache.tika.exception.ZeroByteFileException;
 import org.apache.tika.metadata.Metadata;
 import org.apache.tika.metadata.TikaCoreProperties;
 import org.apache.tika.sax.BodyContentHandler;
-import org.apache.tika.sax.ToXMLContentHandler;
 
 public class ParsingReader extends Reader {
 
@@ -19,37 +16,56 @@ public class ParsingReader extends Reader {
     private final PipedOutputStream pipedOutputStream;
     private final InputStream stream;
     private final Metadata metadata;
+    private final String path;
     private final ParseContext context;
-    private final boolean outputXml;
+    private final OutputFormat outputFormat;
     private final String encoding;
-    private transient Throwable throwable;
-
-    public ParsingReader(Parser parser, InputStream stream, Metadata metadata,
-                            ParseContext context, boolean outputXml, String encoding) throws IOException {
+    private final ParseMonitor monitor;
+    private final Thread thread;
+    private volatile Throwable throwable;
+
+    /**
+     * @param path the path of the file or the url, reported with the errors, null for bytes and
+     *             streams
+     * @param monitor enforces the timeout and the cancellation of the parsing, can be null
+     */
+    public ParsingReader(Parser parser, InputStream stream, Metadata metadata, String path,
+                            ParseContext context, OutputFormat outputFormat, String encoding,
+                            ParseMonitor monitor) throws IOException {
         this.parser = parser;
         this.stream = stream;
         this.metadata = metadata;
+        this.path = path;
         this.context = context;
-        this.outputXml = outputXml;
+        this.outputFormat = outputFormat;
         this.encoding = encoding;
+        this.monitor = monitor;
 
         PipedInputStream pipedInputStream = new PipedInputStream();
         this.pipedOutputStream = new PipedOutputStream(pipedInputStream);
-        this.reader = new BufferedReader(new InputStreamReader(pipedInputStream));
-
-        Executor executor = command -> {
-            String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
-            if (name != null) {
-                name = "Apache Tika: " + name;
-            } else {
-                name = "Apache Tika";
-            }
-            Thread thread = new Thread(command, name);
-            thread.setDaemon(true);
-            thread.start();
-        };
+        this.reader = new BufferedReader(new InputStreamReader(pipedInputStream, encoding));
 
-        executor.execute(new ParsingTask());
+        String name = metadata.get(TikaCoreProperties.RESOURCE_NAME_KEY);
+        if (name != null) {
+            name = "Apache Tika: " + name;
+        } else {
+            name = "Apache Tika";
+        }
+        thread = new Thread(new ParsingTask(), name);
+        thread.setDaemon(true);
+
+        if (monitor != null) {
+            // Aborting ends the output, which unblocks the reads. The parsing thread then fails
+            // at its next write, if it does not stop on the interrupt already
+            monitor.start(() -> {
+                thread.interrupt();
+                try {
+                    pipedOutputStream.close();
+                } catch (IOException ignored) {
+                }
+            });
+        }
+        thread.start();
 
         reader.mark(1);
         reader.read();
@@ -58,18 +74,73 @@ public class ParsingReader extends Reader {
 
     @Override
     public int read(char[] cbuf, int off, int len) throws IOException {
-        if (throwable instanceof ZeroByteFileException) {
-            return -1;
-        } else if (throwable instanceof IOException) {
-            throw (IOException) throwable;
-        } else if (throwable != null) {
-            throw new IOException("", throwable);
+        checkAborted();
+        checkFailed();
+
+        final int length = reader.read(cbuf, off, len);
+        if (length == -1) {
+            // The output also ends when the parsing is aborted or fails
+            checkAborted();
+            checkFailed();
+        }
+        return length;
+    }
+
+    private void checkFailed() throws IOException {
+        final ErrorInfo error = getError();
+        if (error != null) {
+            throw new IOException(error.getMessage(), throwable);
+        }
+    }
+
+    /**
+     * Returns the error of the parsing running in the background
+     * @return ErrorInfo error or
+     * null if there is no error, the parsing may still be running
+     */
+    public ErrorInfo getError() {
+        final Throwable t = throwable;
+        if (t == null) {
+            return null;
+        }
+        return ErrorInfo.of(t, path, metadata);
+    }
+
+    /**
+     * Returns the error of the timeout or the cancellation of the parsing, with the path and
+     * the mime type of the document
+     * @return ErrorInfo error or
+     * null if the parsing was not aborted
+     */
+    public ErrorInfo getAbortError() {
+        if (monitor == null || !monitor.isAborted()) {
+            return null;
+        }
+        return ErrorInfo.of(new ParseMonitor.AbortedException(monitor), path, metadata);
+    }
+
+    /**
+     * Waits for the parsing to end and returns the metadata, which parsers keep filling while
+     * parsing. The output has to be read to its end first, the parsing blocks while the pipe is full
+     * @return the complete tika metadata
+     */
+    public Metadata getFinalMetadata() throws InterruptedException {
+        thread.join();
+        return metadata;
+    }
+
+    private void checkAborted() throws IOException {
+        if (monitor != null && monitor.isAborted()) {
+            throw new IOException(monitor.getMessage());
         }
-        return reader.read(cbuf, off, len);
     }
 
     @Override
     public void close() throws IOException {
+        if (monitor != null) {
+            // Stops the timeout, the parsing thread stops at its next write into the closed pipe
+            monitor.finish();
+        }
         reader.close();
     }
 
@@ -77,12 +148,30 @@ public class ParsingReader extends Reader {
 
         public void run() {
             try {
-                ContentHandler handler = outputXml ? new ToXMLContentHandler(pipedOutputStream, encoding) : new BodyContentHandler(pipedOutputStream);
+                JsonDocumentWriter json = null;
+                ContentHandler handler;
+                if (outputFormat == OutputFormat.JSON) {
+                    json = new JsonDocumentWriter(new OutputStreamWriter(pipedOutputStream, encoding));
+                    handler = new BodyContentHandler(json);
+                } else {
+                    handler = outputFormat.newHandler(pipedOutputStream, encoding);
+                }
+                if (monitor != null) {
+                    handler = monitor.wrap(handler);
+                }
                 parser.parse(stream, handler, metadata, context);
+                if (json != null) {
+                    // The metadata is complete once the parsing is done
+                    json.finish(metadata);
+                }
             } catch (Throwable t) {
                 throwable = t;
             }
 
+            if (monitor != null) {
+                monitor.finish();
+            }
+
             try {
                 stream.close();
             } catch (Throwable t) {
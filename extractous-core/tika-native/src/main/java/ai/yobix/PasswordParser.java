@@ -0,0 +1,154 @@
+package ai.yobix;
+
+import org.apache.tika.detect.Detector;
+import org.apache.tika.exception.EncryptedDocumentException;
+import org.apache.tika.exception.TikaException;
+import org.apache.tika.io.TemporaryResources;
+import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.metadata.Metadata;
+import org.apache.tika.parser.ParseContext;
+import org.apache.tika.parser.Parser;
+import org.apache.tika.parser.ParserDecorator;
+import org.apache.tika.parser.PasswordProvider;
+import org.xml.sax.ContentHandler;
+import org.xml.sax.SAXException;
+import org.xml.sax.helpers.DefaultHandler;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Path;
+import java.util.Arrays;
+import java.util.HashSet;
+import java.util.Set;
+
+/**
+ * Decorates a parser to open encrypted documents with a list of candidate passwords. Set as the
+ * parser of the ParseContext, it also parses the embedded documents, so the candidates are tried
+ * for every document.
+ * A single candidate is simply given to the parser. With more candidates, the start of a
+ * document of a type that can be encrypted is parsed with each of them until one does not fail
+ * with an EncryptedDocumentException, then the document is parsed with that one. The documents of
+ * the other types are given the first candidate without probing.
+ */
+public class PasswordParser extends ParserDecorator {
+
+    /**
+     * The types of the documents that can be encrypted with a password. Encrypted OOXML
+     * documents are detected as an OLE2 container of their own type
+     */
+    private static final Set<String> ENCRYPTABLE_TYPES = new HashSet<>(Arrays.asList(
+            "application/pdf",
+            "application/x-tika-ooxml-protected",
+            "application/x-tika-msoffice",
+            "application/msword",
+            "application/vnd.ms-excel",
+            "application/vnd.ms-powerpoint",
+            "application/zip",
+            "application/x-7z-compressed",
+            "application/x-rar-compressed"
+    ));
+    private static final String ODF_TYPE_PREFIX = "application/vnd.oasis.opendocument.";
+
+    private final Detector detector;
+    private final String[] passwords;
+    private final ParseMonitor monitor;
+
+    /**
+     * @param parser the decorated parser
+     * @param detector detects the type of the documents, only the encryptable ones are probed
+     * @param passwords the candidate passwords, in order
+     * @param monitor the monitor of the parsing, can be null. The probes stop when it aborts
+     */
+    public PasswordParser(Parser parser, Detector detector, String[] passwords, ParseMonitor monitor) {
+        super(parser);
+        this.detector = detector;
+        this.passwords = passwords;
+        this.monitor = monitor;
+    }
+
+    @Override
+    public void parse(InputStream stream, ContentHandler handler, Metadata metadata, ParseContext context)
+            throws IOException, SAXException, TikaException {
+        if (passwords.length == 0) {
+            super.parse(stream, handler, metadata, context);
+            return;
+        }
+
+        // Embedded documents set their own password, the one of the container is restored after
+        final PasswordProvider previous = context.get(PasswordProvider.class);
+        try (final TemporaryResources tmp = new TemporaryResources()) {
+            final TikaInputStream tikaStream = TikaInputStream.get(stream, tmp, metadata);
+            final String password = passwords.length == 1 || !isEncryptable(tikaStream, metadata)
+                    ? passwords[0]
+                    : findPassword(tikaStream, metadata, context);
+
+            context.set(PasswordProvider.class, m -> password);
+            super.parse(tikaStream, handler, metadata, context);
+        } finally {
+            context.set(PasswordProvider.class, previous);
+        }
+    }
+
+    private boolean isEncryptable(TikaInputStream stream, Metadata metadata) throws IOException {
+        // The detector resets the stream, so the parser still gets the whole document
+        final String type = detector.detect(stream, metadata).getBaseType().toString();
+        return ENCRYPTABLE_TYPES.contains(type) || type.startsWith(ODF_TYPE_PREFIX);
+    }
+
+    private String findPassword(TikaInputStream stream, Metadata metadata, ParseContext context)
+            throws IOException, TikaException {
+        // Spools the stream to a file, so it can be read once per candidate
+        final Path path = stream.getPath();
+
+        for (String password : passwords) {
+            checkAborted();
+            context.set(PasswordProvider.class, m -> password);
+            // Under the monitor, the probe stops at its next output once the parsing is aborted
+            final ContentHandler handler = monitor == null
+                    ? new ProbeHandler()
+                    : monitor.wrap(new ProbeHandler());
+            try (final TikaInputStream probe = TikaInputStream.get(path)) {
+                super.parse(probe, handler, copy(metadata), context);
+            } catch (EncryptedDocumentException e) {
+                // Wrong password, try the next one
+                continue;
+            } catch (SAXException | TikaException | IOException e) {
+                // Decrypted, the probe stops at the first text. Other errors are left to the parse
+            }
+            checkAborted();
+            return password;
+        }
+        throw new EncryptedDocumentException(
+                "None of the " + passwords.length + " passwords decrypts the document");
+    }
+
+    private void checkAborted() throws TikaException {
+        if (monitor != null && monitor.isAborted()) {
+            throw new ParseMonitor.AbortedException(monitor);
+        }
+    }
+
+    private static Metadata copy(Metadata metadata) {
+        final Metadata copy = new Metadata();
+        for (String name : metadata.names()) {
+            for (String value : metadata.getValues(name)) {
+                copy.add(name, value);
+            }
+        }
+        return copy;
+    }
+
+    /**
+     * Stops the parsing at the first text, which is only written once the document is decrypted
+     */
+    private static class ProbeHandler extends DefaultHandler {
+        @Override
+        public void characters(char[] ch, int start, int length) throws SAXException {
+            for (int i = start; i < start + length; i++) {
+                if (!Character.isWhitespace(ch[i])) {
+                    throw new SAXException("Decrypted");
+                }
+            }
+        }
+    }
+}
@@ -8,27 +8,39 @@ public class ReaderResult {
     private final ReaderInputStream reader;
     private final byte status;
     private final String errorMessage;
+    private final ErrorInfo error;
     private final Metadata metadata;
+    private final ParsingReader parsingReader;
 
     public ReaderResult(ReaderInputStream reader) {
         this.reader = reader;
         this.status = 0;
         this.errorMessage = null;
+        this.error = null;
         this.metadata = null;
+        this.parsingReader = null;
     }
 
-    public ReaderResult(ReaderInputStream reader, Metadata metadata) {
+    public ReaderResult(ReaderInputStream reader, Metadata metadata, ParsingReader parsingReader) {
         this.reader = reader;
         this.status = 0;
         this.errorMessage = null;
+        this.error = null;
         this.metadata = metadata;
+        this.parsingReader = parsingReader;
     }
 
     public ReaderResult(byte status, String errorMessage) {
+        this(new ErrorInfo(status, errorMessage));
+    }
+
+    public ReaderResult(ErrorInfo error) {
         this.reader = null;
-        this.status = status;
-        this.errorMessage = errorMessage;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
         this.metadata = null;
+        this.parsingReader = null;
     }
 
     /**
@@ -39,6 +51,23 @@ public class ReaderResult {
         return reader;
     }
 
+    /**
+     * Returns the reader running the parsing, which gives the final metadata once the parsing is
+     * done, or null if there is an error
+     * @return ParsingReader parsing reader
+     */
+    public ParsingReader getParsingReader() {
+        return parsingReader;
+    }
+
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
     public boolean isError() {
         return status != 0;
     }
@@ -55,7 +84,7 @@ public class ReaderResult {
      * Returns the status of the call
      * @return
      * 0: OK
-     * 1: IOException
+     * otherwise the status of the error, see ErrorInfo.getStatus
      */
     public byte getStatus() {
         return status;
@@ -0,0 +1,77 @@
+package ai.yobix;
+
+import org.apache.tika.metadata.Metadata;
+
+import java.util.List;
+
+public class RecursiveResult {
+
+    private final Metadata[] metadataList;
+    private final byte status;
+    private final String errorMessage;
+    private final ErrorInfo error;
+
+    public RecursiveResult(List<Metadata> metadataList) {
+        this.metadataList = metadataList.toArray(new Metadata[0]);
+        this.status = 0;
+        this.errorMessage = null;
+        this.error = null;
+    }
+
+    public RecursiveResult(byte status, String errorMessage) {
+        this(new ErrorInfo(status, errorMessage));
+    }
+
+    public RecursiveResult(ErrorInfo error) {
+        this.metadataList = null;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
+    }
+
+    /**
+     * Returns one tika metadata per document, the container document first followed by its
+     * embedded documents. The content of each document is stored under the X-TIKA:content key.
+     * Returns null if there is an error
+     * @return array of tika metadata
+     */
+    public Metadata[] getMetadataList() {
+        return metadataList;
+    }
+
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * otherwise the status of the error, see ErrorInfo.getStatus
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage + " documents: "
+                + (this.metadataList == null ? 0 : this.metadataList.length);
+    }
+}
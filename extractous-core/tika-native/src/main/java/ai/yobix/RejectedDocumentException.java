@@ -0,0 +1,24 @@
+package ai.yobix;
+
+import org.apache.tika.exception.TikaException;
+
+/**
+ * Thrown when the mime type of a document is denied, or not allowed, by the extractor. The
+ * document is rejected before any of its content is parsed
+ */
+public class RejectedDocumentException extends TikaException {
+
+    private final String mimeType;
+
+    public RejectedDocumentException(String mimeType) {
+        super("Documents of type " + mimeType + " are not allowed");
+        this.mimeType = mimeType;
+    }
+
+    /**
+     * Returns the detected mime type of the rejected document
+     */
+    public String getMimeType() {
+        return mimeType;
+    }
+}
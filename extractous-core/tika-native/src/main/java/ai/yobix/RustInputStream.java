@@ -0,0 +1,61 @@
+package ai.yobix;
+
+import java.io.IOException;
+import java.io.InputStream;
+
+/**
+ * InputStream that pulls its bytes in chunks from a Rust reader (std::io::Read).
+ * The native methods are registered by the Rust side before the first stream is created.
+ * The handle is owned by this stream and released exactly once when the stream is closed.
+ */
+public class RustInputStream extends InputStream {
+
+    private long handle;
+
+    public RustInputStream(long handle) {
+        this.handle = handle;
+    }
+
+    @Override
+    public int read() throws IOException {
+        final byte[] b = new byte[1];
+        final int length = read(b, 0, 1);
+        if (length == -1) {
+            return -1;
+        }
+
+        return (b[0] & 0xFF);   // need to be in the range 0 to 255
+    }
+
+    @Override
+    public synchronized int read(byte[] b, int off, int len) throws IOException {
+
+        if (handle == 0) {
+            throw new IOException("read on a closed InputStream");
+        }
+
+        if (b == null) {
+            throw new NullPointerException();
+        } else if (off < 0 || len < 0 || len > b.length - off) {
+            throw new IndexOutOfBoundsException();
+        } else if (len == 0) {
+            return 0;
+        }
+
+        // Returns -1 once the Rust reader reached its end
+        return nativeRead(handle, b, off, len);
+    }
+
+    @Override
+    public synchronized void close() throws IOException {
+        if (handle != 0) {
+            nativeClose(handle);
+            handle = 0;
+        }
+    }
+
+    private static native int nativeRead(long handle, byte[] b, int off, int len) throws IOException;
+
+    private static native void nativeClose(long handle);
+
+}
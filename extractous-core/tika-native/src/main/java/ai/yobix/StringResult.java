@@ -7,12 +7,14 @@ public class StringResult {
     private final String content;
     private final byte status;
     private final String errorMessage;
+    private final ErrorInfo error;
     private final Metadata metadata;
 
     public StringResult(String content) {
         this.content = content;
         this.status = 0;
         this.errorMessage = null;
+        this.error = null;
         this.metadata = null;
     }
 
@@ -20,13 +22,19 @@ public class StringResult {
         this.content = content;
         this.status = 0;
         this.errorMessage = null;
+        this.error = null;
         this.metadata = metadata;
     }
 
     public StringResult(byte status, String errorMessage) {
+        this(new ErrorInfo(status, errorMessage));
+    }
+
+    public StringResult(ErrorInfo error) {
         this.content = null;
-        this.status = status;
-        this.errorMessage = errorMessage;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
         this.metadata = null;
     }
 
@@ -38,6 +46,14 @@ public class StringResult {
         return content;
     }
 
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
     public boolean isError() {
         return status != 0;
     }
@@ -54,8 +70,7 @@ public class StringResult {
      * Returns the status of the call
      * @return
      * 0: OK
-     * 1: IOException
-     * 2: TikaException
+     * otherwise the status of the error, see ErrorInfo.getStatus
      */
     public byte getStatus() {
         return status;
@@ -0,0 +1,68 @@
+package ai.yobix;
+
+import org.apache.tika.config.TikaConfig;
+
+public class TikaConfigResult {
+
+    private final TikaConfig config;
+    private final byte status;
+    private final String errorMessage;
+    private final ErrorInfo error;
+
+    public TikaConfigResult(TikaConfig config) {
+        this.config = config;
+        this.status = 0;
+        this.errorMessage = null;
+        this.error = null;
+    }
+
+    public TikaConfigResult(ErrorInfo error) {
+        this.config = null;
+        this.status = error.getStatus();
+        this.errorMessage = error.getMessage();
+        this.error = error;
+    }
+
+    /**
+     * Returns the loaded tika config, or null if there is an error
+     * @return TikaConfig config
+     */
+    public TikaConfig getConfig() {
+        return config;
+    }
+
+    /**
+     * Returns the details of the error, or null if there is no error
+     * @return ErrorInfo error
+     */
+    public ErrorInfo getError() {
+        return error;
+    }
+
+    public boolean isError() {
+        return status != 0;
+    }
+
+    /**
+     * Returns the status of the call
+     * @return
+     * 0: OK
+     * 16: INVALID_CONFIG, the config could not be loaded
+     */
+    public byte getStatus() {
+        return status;
+    }
+
+    /**
+     * Returns the error message in case of error
+     * @return  String representing the error message or
+     * null if there is no error
+     */
+    public String getErrorMessage() {
+        return errorMessage;
+    }
+
+    public String toString() {
+        return "status:" + this.status + " error: " + this.errorMessage;
+    }
+}
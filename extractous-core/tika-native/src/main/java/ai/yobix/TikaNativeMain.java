@@ -1,22 +1,28 @@
 package ai.yobix;
 
+import org.apache.commons.io.IOUtils;
 import org.apache.commons.io.input.ReaderInputStream;
 import org.apache.tika.Tika;
 import org.apache.tika.config.TikaConfig;
+import org.apache.tika.detect.Detector;
 import org.apache.tika.exception.TikaException;
 import org.apache.tika.exception.WriteLimitReachedException;
 import org.apache.tika.io.TemporaryResources;
 import org.apache.tika.io.TikaInputStream;
+import org.apache.tika.langdetect.optimaize.OptimaizeLangDetector;
+import org.apache.tika.language.detect.LanguageDetector;
+import org.apache.tika.language.detect.LanguageResult;
 import org.apache.tika.metadata.Metadata;
-import org.apache.tika.parser.AutoDetectParser;
+import org.apache.tika.metadata.TikaCoreProperties;
+import org.apache.tika.mime.MediaType;
+import org.apache.tika.mime.MimeTypes;
 import org.apache.tika.parser.ParseContext;
 import org.apache.tika.parser.Parser;
-import org.apache.tika.parser.microsoft.OfficeParserConfig;
-import org.apache.tika.parser.ocr.TesseractOCRConfig;
-import org.apache.tika.parser.pdf.PDFParserConfig;
+import org.apache.tika.parser.RecursiveParserWrapper;
+import org.apache.tika.sax.BasicContentHandlerFactory;
 import org.apache.tika.sax.BodyContentHandler;
-import org.apache.tika.sax.ToXMLContentHandler;
-import org.apache.tika.sax.WriteOutContentHandler;
+import org.apache.tika.sax.ContentHandlerFactory;
+import org.apache.tika.sax.RecursiveParserWrapperHandler;
 import org.graalvm.nativeimage.IsolateThread;
 import org.graalvm.nativeimage.c.function.CEntryPoint;
 import org.graalvm.nativeimage.c.type.CCharPointer;
@@ -25,10 +31,9 @@ import org.graalvm.nativeimage.c.type.CTypeConversion;
 import org.xml.sax.ContentHandler;
 import org.xml.sax.SAXException;
 
+import java.io.ByteArrayInputStream;
 import java.io.IOException;
 import java.io.InputStream;
-import java.io.Reader;
-import java.net.MalformedURLException;
 import java.net.URI;
 import java.net.URISyntaxException;
 import java.net.URL;
@@ -37,27 +42,175 @@ import java.nio.charset.Charset;
 import java.nio.charset.StandardCharsets;
 import java.nio.file.Path;
 import java.nio.file.Paths;
+import java.util.List;
+import java.util.stream.Collectors;
 
 public class TikaNativeMain {
 
     private static final Tika tika = new Tika();
+    private static final MimeTypes mimeTypes = MimeTypes.getDefaultMimeTypes();
+    private static LanguageDetector languageDetector;
 
     /**
-     * Parses the given file and returns its type as a mime type
+     * Loads a tika config from the content of a tika-config.xml. The config is loaded once and
+     * given to the detect and parse calls, which use the default config when given null
      *
-     * @param filePath: the path of the file to be parsed
-     * @return StringResult
+     * @param xml the content of the tika-config.xml
+     * @return TikaConfigResult
+     */
+    public static TikaConfigResult loadTikaConfig(String xml) {
+        try (final InputStream stream = new ByteArrayInputStream(xml.getBytes(StandardCharsets.UTF_8))) {
+            return new TikaConfigResult(new TikaConfig(stream));
+        } catch (IOException | TikaException | SAXException | RuntimeException e) {
+            // Unknown classes and bad parameters fail with runtime exceptions
+            return new TikaConfigResult(ErrorInfo.of(ErrorInfo.INVALID_CONFIG, e, null, null));
+        }
+    }
+
+    private static TikaConfig orDefault(TikaConfig tikaConfig) {
+        return tikaConfig != null ? tikaConfig : TikaConfig.getDefaultConfig();
+    }
+
+    /**
+     * Opens the given url. The hints of the caller take precedence over the name, the type and
+     * the encoding given by the server
+     */
+    private static TikaInputStream openUrl(URL url, Metadata metadata) throws IOException {
+        final Metadata hints = new Metadata();
+        for (String name : metadata.names()) {
+            hints.set(name, metadata.getValues(name));
+        }
+        final TikaInputStream stream = TikaInputStream.get(url, metadata);
+        for (String name : hints.names()) {
+            metadata.set(name, hints.getValues(name));
+        }
+        return stream;
+    }
+
+    /**
+     * Detects the mime type of the given file without parsing it
+     *
+     * @param filePath: the path of the file to be detected
+     * @param metadata: the metadata of the document, with the hints such as its file name
+     * @param tikaConfig: the config loaded by loadTikaConfig, null for the default config
+     * @return DetectResult
      */
-    public static StringResult detect(String filePath) {
+    public static DetectResult detectFile(String filePath, Metadata metadata, TikaConfig tikaConfig) {
         final Path path = Paths.get(filePath);
-        final Metadata metadata = new Metadata();
 
-        try (final InputStream stream = TikaInputStream.get(path, metadata)) {
-            final String result = tika.detect(stream, metadata);
-            return new StringResult(result, metadata);
+        try (final TikaInputStream stream = TikaInputStream.get(path, metadata)) {
+            return detect(stream, metadata, tikaConfig);
+        } catch (IOException e) {
+            return new DetectResult(ErrorInfo.of(e, filePath, metadata));
+        }
+    }
 
-        } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, e.getMessage());
+    /**
+     * Detects the mime type of the given Url without parsing it
+     *
+     * @param urlString the url to be detected
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param tikaConfig the config loaded by loadTikaConfig, null for the default config
+     * @return DetectResult
+     */
+    public static DetectResult detectUrl(String urlString, Metadata metadata, TikaConfig tikaConfig) {
+        try {
+            final URL url = new URI(urlString).toURL();
+
+            try (final TikaInputStream stream = openUrl(url, metadata)) {
+                return detect(stream, metadata, tikaConfig);
+            }
+        } catch (IOException | URISyntaxException e) {
+            return new DetectResult(ErrorInfo.of(e, urlString, metadata));
+        }
+    }
+
+    /**
+     * Detects the mime type of the given array of bytes without parsing it
+     *
+     * @param data an array of bytes
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param tikaConfig the config loaded by loadTikaConfig, null for the default config
+     * @return DetectResult
+     */
+    public static DetectResult detectBytes(ByteBuffer data, Metadata metadata, TikaConfig tikaConfig) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+
+        try (final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata)) {
+            return detect(stream, metadata, tikaConfig);
+        } catch (IOException e) {
+            return new DetectResult(ErrorInfo.of(e, null, metadata));
+        }
+    }
+
+    private static DetectResult detect(TikaInputStream stream, Metadata metadata, TikaConfig tikaConfig)
+            throws IOException {
+        final Detector detector = orDefault(tikaConfig).getDetector();
+        final MediaType detected = detector.detect(stream, metadata);
+
+        // Run the detection again with less information to find out which step decided the type.
+        // The stream is reset by the detectors, so it can be read multiple times
+        final DetectResult.Source source;
+        if (MediaType.OCTET_STREAM.equals(detected)) {
+            source = DetectResult.Source.UNKNOWN;
+        } else if (detected.equals(mimeTypes.detect(stream, new Metadata()))) {
+            source = DetectResult.Source.MAGIC;
+        } else if (detected.equals(detector.detect(stream, new Metadata()))) {
+            source = DetectResult.Source.CONTAINER;
+        } else {
+            source = DetectResult.Source.FILENAME;
+        }
+
+        metadata.set(Metadata.CONTENT_TYPE, detected.toString());
+        return new DetectResult(detected.toString(), source, metadata);
+    }
+
+    /**
+     * Detects the languages of the given text, for example the extracted content of a document
+     * or of one of its pages
+     *
+     * @param text the text to be detected
+     * @return LanguageDetectionResult with the languages found in the text, most probable first
+     */
+    public static LanguageDetectionResult detectLanguages(String text) {
+        final List<LanguageResult> known;
+        try {
+            known = detectKnownLanguages(text);
+        } catch (IOException e) {
+            return new LanguageDetectionResult(ErrorInfo.of(e, null, null));
+        }
+
+        final String[] languages = new String[known.size()];
+        final float[] confidences = new float[known.size()];
+        for (int i = 0; i < known.size(); i++) {
+            languages[i] = known.get(i).getLanguage();
+            confidences[i] = known.get(i).getRawScore();
+        }
+        return new LanguageDetectionResult(languages, confidences);
+    }
+
+    /**
+     * Returns the languages found in the given text, most probable first
+     */
+    static List<LanguageResult> detectKnownLanguages(String text) throws IOException {
+        final LanguageDetector detector = languageDetector();
+        final List<LanguageResult> results;
+        // The detector keeps the text it is given, calls must not be interleaved
+        synchronized (detector) {
+            results = detector.detectAll(text);
+        }
+        return results.stream()
+                .filter(result -> !result.isUnknown())
+                .collect(Collectors.toList());
+    }
+
+    private static LanguageDetector languageDetector() throws IOException {
+        // Loading the language models takes a while, they are loaded once on first use
+        synchronized (TikaNativeMain.class) {
+            if (languageDetector == null) {
+                languageDetector = new OptimaizeLangDetector().loadModels();
+            }
+            return languageDetector;
         }
     }
 
@@ -67,31 +220,20 @@ public class TikaNativeMain {
      * first characters extracted from the input document.
      *
      * @param filePath:  the path of the file to be parsed
-     * @param maxLength: maximum length of the returned string
+     * @param metadata:  the metadata of the document, with the hints such as its file name
+     * @param settings:  the settings of the extraction, with the maximum length of the string
      * @return StringResult
      */
-    public static StringResult parseFileToString(
-            String filePath,
-            int maxLength,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-            // maybe replace with a single config class
-    ) {
+    public static StringResult parseFileToString(String filePath, Metadata metadata, ParseSettings settings) {
         try {
             final Path path = Paths.get(filePath);
-            final Metadata metadata = new Metadata();
             final InputStream stream = TikaInputStream.get(path, metadata);
 
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+            String result = parseToString(stream, metadata, settings);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
-        } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "Could not open file: " + e.getMessage());
-        } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        } catch (IOException | TikaException e) {
+            return new StringResult(ErrorInfo.of(e, filePath, metadata));
         }
     }
 
@@ -99,34 +241,21 @@ public class TikaNativeMain {
      * Parses the given Url and returns its content as String
      *
      * @param urlString the url to be parsed
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction
      * @return StringResult
      */
-    public static StringResult parseUrlToString(
-            String urlString,
-            int maxLength,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
+    public static StringResult parseUrlToString(String urlString, Metadata metadata, ParseSettings settings) {
         try {
             final URL url = new URI(urlString).toURL();
-            final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = openUrl(url, metadata);
 
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+            String result = parseToString(stream, metadata, settings);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
 
-        } catch (MalformedURLException e) {
-            return new StringResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
-        } catch (URISyntaxException e) {
-            return new StringResult((byte) 2, "Malformed URI error occurred: " + e.getMessage());
-        } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
-        } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        } catch (IOException | URISyntaxException | TikaException e) {
+            return new StringResult(ErrorInfo.of(e, urlString, metadata));
         }
     }
 
@@ -134,106 +263,226 @@ public class TikaNativeMain {
      * Parses the given array of bytes and return its content as String.
      *
      * @param data an array of bytes
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction
      * @return StringResult
      */
-    public static StringResult parseBytesToString(
-            ByteBuffer data,
-            int maxLength,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
-        final Metadata metadata = new Metadata();
+    public static StringResult parseBytesToString(ByteBuffer data, Metadata metadata, ParseSettings settings) {
         final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
         final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
 
         try {
-            String result = parseToStringWithConfig(
-                    stream, metadata, maxLength, pdfConfig, officeConfig, tesseractConfig, asXML);
+            String result = parseToString(stream, metadata, settings);
             // No need to close the stream because parseToString does so
             return new StringResult(result, metadata);
-        } catch (java.io.IOException e) {
-            return new StringResult((byte) 1, "IO error occurred: " + e.getMessage());
-        } catch (TikaException e) {
-            return new StringResult((byte) 2, "Parse error occurred : " + e.getMessage());
+        } catch (IOException | TikaException e) {
+            return new StringResult(ErrorInfo.of(e, null, metadata));
         }
     }
 
-    private static String parseToStringWithConfig(
-            InputStream stream,
-            Metadata metadata,
-            int maxLength,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) throws IOException, TikaException {
-        ContentHandler handler;
-        ContentHandler handlerForParser;
-        if (asXML) {
-            handler = new WriteOutContentHandler(new ToXMLContentHandler(), maxLength);
-            handlerForParser = handler;
-        } else {
-            handler = new WriteOutContentHandler(maxLength);
-            handlerForParser = new BodyContentHandler(handler);
-        }
+    /**
+     * Parses the given InputStream and return its content as String.
+     * The stream is read in chunks and is closed once parsing is done
+     *
+     * @param inputStream the stream to be parsed
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction
+     * @return StringResult
+     */
+    public static StringResult parseInputStreamToString(
+            InputStream inputStream, Metadata metadata, ParseSettings settings) {
+        final TikaInputStream stream = TikaInputStream.get(inputStream, new TemporaryResources(), metadata);
 
         try {
-            final TikaConfig config = TikaConfig.getDefaultConfig();
-            final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
+            String result = parseToString(stream, metadata, settings);
+            // No need to close the stream because parseToString does so
+            return new StringResult(result, metadata);
+        } catch (IOException | TikaException e) {
+            return new StringResult(ErrorInfo.of(e, null, metadata));
+        }
+    }
 
+    private static String parseToString(InputStream stream, Metadata metadata, ParseSettings settings)
+            throws IOException, TikaException {
+        final OutputFormat format = settings.getOutputFormat();
+        final ContentHandler output = format.newHandler(settings.getMaxLength());
+        final LanguageSampleHandler languageSample =
+                settings.isDetectLanguages() ? new LanguageSampleHandler(output) : null;
+        final ContentHandler handler = languageSample != null ? languageSample : output;
+        final ParseMonitor monitor = settings.getMonitor();
+
+        try {
+            final ParseContext parsecontext = settings.newParseContext();
+            final Parser parser = settings.newParser();
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
-            parsecontext.set(OfficeParserConfig.class, officeConfig);
-            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
 
-            parser.parse(stream, handlerForParser, metadata, parsecontext);
+            if (monitor != null) {
+                final ContentHandler monitoredHandler = monitor.wrap(handler);
+                monitor.run(() -> parser.parse(stream, monitoredHandler, metadata, parsecontext));
+            } else {
+                parser.parse(stream, handler, metadata, parsecontext);
+            }
         } catch (SAXException e) {
             if (!WriteLimitReachedException.isWriteLimitReached(e)) {
                 // This should never happen with BodyContentHandler...
                 throw new TikaException("Unexpected SAX processing failure", e);
             }
+            // Flags the content as truncated, the same way the recursive parsing does
+            metadata.set(TikaCoreProperties.WRITE_LIMIT_REACHED, true);
         } finally {
             stream.close();
         }
-        return handler.toString();
+        if (languageSample != null) {
+            // Before writing the JSON, which includes the metadata
+            languageSample.addLanguages(metadata);
+        }
+        if (format == OutputFormat.JSON) {
+            return JsonDocumentWriter.toJson(output.toString(), metadata);
+        }
+        return output.toString();
     }
 
 
+    /**
+     * Parses the given file and its embedded documents recursively. Each document gets its own
+     * metadata, content, embedded resource path and depth.
+     *
+     * @param filePath:  the path of the file to be parsed
+     * @param metadata:  the metadata of the document, with the hints such as its file name
+     * @param settings:  the settings of the extraction, the maximum length applies to every document
+     * @return RecursiveResult
+     */
+    public static RecursiveResult parseFileRecursive(String filePath, Metadata metadata, ParseSettings settings) {
+        try {
+            final Path path = Paths.get(filePath);
+            final InputStream stream = TikaInputStream.get(path, metadata);
+
+            return parseRecursive(stream, metadata, settings);
+        } catch (IOException | TikaException e) {
+            return new RecursiveResult(ErrorInfo.of(e, filePath, metadata));
+        }
+    }
+
+    /**
+     * Parses the given Url and its embedded documents recursively.
+     *
+     * @param urlString the url to be parsed
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction
+     * @return RecursiveResult
+     */
+    public static RecursiveResult parseUrlRecursive(String urlString, Metadata metadata, ParseSettings settings) {
+        try {
+            final URL url = new URI(urlString).toURL();
+            final TikaInputStream stream = openUrl(url, metadata);
+
+            return parseRecursive(stream, metadata, settings);
+        } catch (IOException | URISyntaxException | TikaException e) {
+            return new RecursiveResult(ErrorInfo.of(e, urlString, metadata));
+        }
+    }
+
+    /**
+     * Parses the given array of bytes and its embedded documents recursively.
+     *
+     * @param data an array of bytes
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction
+     * @return RecursiveResult
+     */
+    public static RecursiveResult parseBytesRecursive(ByteBuffer data, Metadata metadata, ParseSettings settings) {
+        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
+        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
+
+        try {
+            return parseRecursive(stream, metadata, settings);
+        } catch (IOException | TikaException e) {
+            return new RecursiveResult(ErrorInfo.of(e, null, metadata));
+        }
+    }
+
+    private static RecursiveResult parseRecursive(InputStream stream, Metadata metadata, ParseSettings settings)
+            throws IOException, TikaException {
+        // The write limit applies to every document separately and does not abort the parsing
+        // of the remaining embedded documents
+        final OutputFormat format = settings.getOutputFormat();
+        final ParseMonitor monitor = settings.getMonitor();
+        final ParseContext parsecontext = settings.newParseContext();
+        final ContentHandlerFactory factory = new BasicContentHandlerFactory(
+                format.recursiveHandlerType(), settings.getMaxLength(), false, parsecontext) {
+            @Override
+            public ContentHandler getNewContentHandler() {
+                ContentHandler handler = super.getNewContentHandler();
+                if (format == OutputFormat.BODY_XHTML) {
+                    handler = new BodyContentHandler(handler);
+                }
+                // The sample sees the body element, which the body handler strips
+                return settings.isDetectLanguages() ? new LanguageSampleHandler(handler) : handler;
+            }
+        };
+        final RecursiveParserWrapperHandler handler = new RecursiveParserWrapperHandler(factory) {
+            @Override
+            public void endEmbeddedDocument(ContentHandler contentHandler, Metadata metadata) throws SAXException {
+                addLanguages(contentHandler, metadata);
+                super.endEmbeddedDocument(contentHandler, metadata);
+            }
+
+            @Override
+            public void endDocument(ContentHandler contentHandler, Metadata metadata) throws SAXException {
+                addLanguages(contentHandler, metadata);
+                super.endDocument(contentHandler, metadata);
+            }
+
+            private void addLanguages(ContentHandler contentHandler, Metadata metadata) throws SAXException {
+                if (contentHandler instanceof LanguageSampleHandler) {
+                    try {
+                        ((LanguageSampleHandler) contentHandler).addLanguages(metadata);
+                    } catch (IOException e) {
+                        throw new SAXException("Failed to load the language models", e);
+                    }
+                }
+            }
+        };
+
+        try {
+            final Parser parser = new RecursiveParserWrapper(settings.newParser());
+
+            if (monitor != null) {
+                monitor.run(() -> parser.parse(stream, handler, metadata, parsecontext));
+            } else {
+                parser.parse(stream, handler, metadata, parsecontext);
+            }
+        } catch (SAXException e) {
+            throw new TikaException("Unexpected SAX processing failure", e);
+        } finally {
+            stream.close();
+        }
+        return new RecursiveResult(handler.getMetadataList());
+    }
+
     /**
      * Parses the given file and returns its content as Reader. The reader can be used
      * to read chunks and must be closed when reading is finished
      *
      * @param filePath the path of the file
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction, with the encoding of the stream
      * @return ReaderResult
      */
-    public static ReaderResult parseFile(
-            String filePath,
-            String charsetName,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
-        try {
-//            System.out.println("pdfConfig.isExtractInlineImages = " + pdfConfig.isExtractInlineImages());
-//            System.out.println("pdfConfig.isExtractMarkedContent = " + pdfConfig.isExtractMarkedContent());
-//            System.out.println("pdfConfig.getOcrStrategy = " + pdfConfig.getOcrStrategy());
-//            System.out.println("officeConfig.isIncludeHeadersAndFooters = " + officeConfig.isIncludeHeadersAndFooters());
-//            System.out.println("officeConfig.isIncludeShapeBasedContent = " + officeConfig.isIncludeShapeBasedContent());
-//            System.out.println("ocrConfig.getTimeoutSeconds = " + tesseractConfig.getTimeoutSeconds());
-//            System.out.println("ocrConfig.language = " + tesseractConfig.getLanguage());
+    public static ReaderResult parseFile(String filePath, Metadata metadata, ParseSettings settings) {
+        final Charset charset = charsetForName(settings.getCharsetName());
+        if (charset == null) {
+            return unsupportedEncoding(settings.getCharsetName());
+        }
 
+        try {
             final Path path = Paths.get(filePath);
-            final Metadata metadata = new Metadata();
             final TikaInputStream stream = TikaInputStream.get(path, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, filePath, charset, settings);
 
-        } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "Could not open file: " + e.getMessage());
+        } catch (IOException e) {
+            return new ReaderResult(ErrorInfo.of(e, filePath, metadata));
         }
     }
 
@@ -242,79 +491,79 @@ public class TikaNativeMain {
      * to read chunks and must be closed when reading is finished
      *
      * @param urlString the url to be parsed
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction, with the encoding of the stream
      * @return ReaderResult
      */
-    public static ReaderResult parseUrl(
-            String urlString,
-            String charsetName,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
+    public static ReaderResult parseUrl(String urlString, Metadata metadata, ParseSettings settings) {
+        // Checked before opening the connection, which only the parsing closes
+        final Charset charset = charsetForName(settings.getCharsetName());
+        if (charset == null) {
+            return unsupportedEncoding(settings.getCharsetName());
+        }
+
         try {
             final URL url = new URI(urlString).toURL();
-            final Metadata metadata = new Metadata();
-            final TikaInputStream stream = TikaInputStream.get(url, metadata);
+            final TikaInputStream stream = openUrl(url, metadata);
 
-            return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+            return parse(stream, metadata, urlString, charset, settings);
 
-        } catch (MalformedURLException e) {
-            return new ReaderResult((byte) 2, "Malformed URL error occurred " + e.getMessage());
-        } catch (URISyntaxException e) {
-            return new ReaderResult((byte) 3, "Malformed URI error occurred: " + e.getMessage());
-        } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (IOException | URISyntaxException e) {
+            return new ReaderResult(ErrorInfo.of(e, urlString, metadata));
         }
     }
 
     /**
-     * Parses the given array of bytes and return its content as Reader. The reader can be used
-     * to read chunks and must be closed when reading is finished
+     * Parses the given InputStream and return its content as Reader. The reader can be used
+     * to read chunks and must be closed when reading is finished. The stream is read in chunks
+     * by the background parsing thread and is closed once parsing is done
      *
-     * @param data an array of bytes
+     * @param inputStream the stream to be parsed
+     * @param metadata the metadata of the document, with the hints such as its file name
+     * @param settings the settings of the extraction, with the encoding of the stream
      * @return ReaderResult
      */
-    public static ReaderResult parseBytes(
-            ByteBuffer data,
-            String charsetName,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
+    public static ReaderResult parseInputStream(InputStream inputStream, Metadata metadata, ParseSettings settings) {
+        final Charset charset = charsetForName(settings.getCharsetName());
+        if (charset == null) {
+            // The stream is ours to close, as the parsing would have done
+            IOUtils.closeQuietly(inputStream);
+            return unsupportedEncoding(settings.getCharsetName());
+        }
 
+        final TikaInputStream stream = TikaInputStream.get(inputStream, new TemporaryResources(), metadata);
 
-        final Metadata metadata = new Metadata();
-        final ByteBufferInputStream inStream = new ByteBufferInputStream(data);
-        final TikaInputStream stream = TikaInputStream.get(inStream, new TemporaryResources(), metadata);
+        return parse(stream, metadata, null, charset, settings);
+    }
+
+    /**
+     * Returns the charset of the given name, or null if the name is not a supported charset
+     */
+    private static Charset charsetForName(String charsetName) {
+        try {
+            return Charset.forName(charsetName);
+        } catch (IllegalArgumentException e) {
+            // Both IllegalCharsetNameException and UnsupportedCharsetException
+            return null;
+        }
+    }
 
-        return parse(stream, metadata, charsetName, pdfConfig, officeConfig, tesseractConfig, asXML);
+    private static ReaderResult unsupportedEncoding(String charsetName) {
+        return new ReaderResult(ErrorInfo.UNSUPPORTED_ENCODING, "Unsupported encoding: " + charsetName);
     }
 
     private static ReaderResult parse(
-            TikaInputStream inputStream,
-            Metadata metadata,
-            String charsetName,
-            PDFParserConfig pdfConfig,
-            OfficeParserConfig officeConfig,
-            TesseractOCRConfig tesseractConfig,
-            boolean asXML
-    ) {
+            TikaInputStream inputStream, Metadata metadata, String path, Charset charset, ParseSettings settings) {
         try {
 
-            final TikaConfig config = TikaConfig.getDefaultConfig();
-            final ParseContext parsecontext = new ParseContext();
-            final Parser parser = new AutoDetectParser(config);
-            final Charset charset = Charset.forName(charsetName, StandardCharsets.UTF_8);
-
+            final ParseContext parsecontext = settings.newParseContext();
+            final Parser parser = settings.newParser();
             parsecontext.set(Parser.class, parser);
-            parsecontext.set(PDFParserConfig.class, pdfConfig);
-            parsecontext.set(OfficeParserConfig.class, officeConfig);
-            parsecontext.set(TesseractOCRConfig.class, tesseractConfig);
 
             //final Reader reader = new org.apache.tika.parser.ParsingReader(parser, inputStream, metadata, parsecontext);
-            final Reader reader = new ParsingReader(parser, inputStream, metadata, parsecontext, asXML, charset.name());
+            final ParsingReader reader = new ParsingReader(
+                    parser, inputStream, metadata, path, parsecontext, settings.getOutputFormat(), charset.name(),
+                    settings.getMonitor());
 
             // Convert Reader which works with chars to ReaderInputStream which works with bytes
             ReaderInputStream readerInputStream = ReaderInputStream.builder()
@@ -322,10 +571,10 @@ public class TikaNativeMain {
                     .setCharset(charset)
                     .get();
 
-            return new ReaderResult(readerInputStream, metadata);
+            return new ReaderResult(readerInputStream, metadata, reader);
 
-        } catch (java.io.IOException e) {
-            return new ReaderResult((byte) 1, "IO error occurred: " + e.getMessage());
+        } catch (IOException e) {
+            return new ReaderResult(ErrorInfo.of(e, path, metadata));
         }
 
     }